@@ -1,9 +1,10 @@
 use bytemuck::{cast_slice, from_bytes};
-use glam::Vec3A;
+use glam::{Vec3A, Vec4};
 use rkyv::AlignedVec;
 use shared::{
-    mesh::Mesh, vertex_parameters::VertexParametersList, IndexList, TriangleIndices, VertexList,
-    VERTEX_MAX_PARAMETERS,
+    mesh::{AlphaMode, Mesh},
+    vertex_parameters::VertexParametersList,
+    IndexList, TriangleIndices, VertexList, VERTEX_MAX_PARAMETERS,
 };
 
 use crate::{
@@ -20,6 +21,7 @@ pub struct MeshOutput {
     pub indices: Vec<TriangleIndices>,
     pub parameters: Vec<f32>,
     pub attribute_count: usize,
+    pub alpha_mode: AlphaMode,
 }
 
 impl MeshOutput {
@@ -55,6 +57,7 @@ impl MeshOutput {
             vertices: VertexList(self.vertices.clone().into_boxed_slice()),
             indices: IndexList(self.indices.clone().into_boxed_slice()),
             parameters: VertexParametersList::from_flat_slice(&self.parameters),
+            alpha_mode: self.alpha_mode,
         };
 
         rkyv::to_bytes::<_, 256>(&mesh).unwrap()
@@ -69,7 +72,9 @@ impl MeshOutput {
         let name = self.name.to_uppercase();
         let p = self.attribute_count;
 
-        format!("pub const {name}: &MeshBytes<{p}> = &MeshBytes(include_bytes!(\"{filename}\"));\n")
+        format!(
+            "pub const {name}: &MeshBytes<{p}> = &MeshBytes(include_archive!(\"{filename}\"));\n"
+        )
     }
 }
 
@@ -113,6 +118,8 @@ pub fn generate_meshes(config: &AssetList) -> String {
         let mut joints_length = 0;
 
         let mut vertex_count: usize = 0;
+        let mut alpha_mode = None;
+        let mut needs_tangents = false;
 
         let material_count = document.materials().count();
 
@@ -130,6 +137,16 @@ pub fn generate_meshes(config: &AssetList) -> String {
                 )
             }
 
+            // Every primitive is merged into one mesh, so they share the first's alpha mode
+            let primitive_alpha = material_alpha_mode(&primitive.material());
+            match alpha_mode {
+                None => alpha_mode = Some(primitive_alpha),
+                Some(mode) if mode != primitive_alpha => println!(
+                    "Alpha modes differ ({mode:?}, {primitive_alpha:?}), mesh may not be rendered correctly"
+                ),
+                Some(_) => (),
+            }
+
             for (kind, attribute) in primitive.attributes() {
                 if attribute.view().unwrap().buffer().index() != 0 {
                     panic!("wrong buffer index");
@@ -176,8 +193,9 @@ pub fn generate_meshes(config: &AssetList) -> String {
                     gltf::Semantic::Tangents => {
                         let view: &[f32] = cast_slice(view);
 
-                        for t in view.chunks_exact(3) {
-                            tangents.push(Vec3A::from_slice(t))
+                        // The w component holds the handedness of the bitangent
+                        for t in view.chunks_exact(4) {
+                            tangents.push(Vec4::from_slice(t))
                         }
                     }
                     gltf::Semantic::Weights(w) => {
@@ -271,11 +289,17 @@ pub fn generate_meshes(config: &AssetList) -> String {
                 }
             };
 
-            // Generate Tangents
-            // TODO
+            // Tangents are only needed for normal mapping
+            needs_tangents |= primitive.material().normal_texture().is_some();
 
             vertex_count = positions.len();
         }
+
+        // Generate Tangents, once every mesh has been merged so they line up with the vertices
+        if needs_tangents && tangents.len() < positions.len() && uvs.len() == positions.len() {
+            println!("Tangents not found, manually generating them...");
+            tangents = generate_tangents(&positions, &normals, &uvs, &indices);
+        }
         let mut parameters = Vec::<Vec<f32>>::new();
 
         positions.iter().enumerate().for_each(|(index, _)| {
@@ -299,9 +323,7 @@ pub fn generate_meshes(config: &AssetList) -> String {
             }
 
             if let Some(tangent) = tangents.get(index) {
-                this_vertex_parameters.push(tangent.x);
-                this_vertex_parameters.push(tangent.y);
-                this_vertex_parameters.push(tangent.z);
+                this_vertex_parameters.extend(tangent.to_array());
             }
 
             parameters.push(this_vertex_parameters);
@@ -321,7 +343,7 @@ pub fn generate_meshes(config: &AssetList) -> String {
             attribute_count += 3;
         }
         if !tangents.is_empty() {
-            attribute_count += 3;
+            attribute_count += 4;
         }
 
         let static_mesh = MeshOutput {
@@ -330,6 +352,7 @@ pub fn generate_meshes(config: &AssetList) -> String {
             indices,
             parameters,
             attribute_count,
+            alpha_mode: alpha_mode.unwrap_or_default(),
         };
 
         out.push_str(&static_mesh.to_output(config));
@@ -395,7 +418,7 @@ pub fn generate_meshes(config: &AssetList) -> String {
         };
 
         // Handle output Images (if they exist)
-        handle_glb_images(images, &mut out, config, filename);
+        handle_glb_images(&document, images, &mut out, config, filename);
 
         println!("### Finished Importing {filename} ###");
     });
@@ -405,6 +428,15 @@ pub fn generate_meshes(config: &AssetList) -> String {
     out
 }
 
+fn material_alpha_mode(material: &gltf::Material) -> AlphaMode {
+    match material.alpha_mode() {
+        gltf::material::AlphaMode::Opaque => AlphaMode::Opaque,
+        // glTF's default cutoff is 0.5
+        gltf::material::AlphaMode::Mask => AlphaMode::Mask(material.alpha_cutoff().unwrap_or(0.5)),
+        gltf::material::AlphaMode::Blend => AlphaMode::Blend,
+    }
+}
+
 // Function to calculate the normal of a triangle given its vertices
 fn calculate_triangle_normal(v0: Vec3A, v1: Vec3A, v2: Vec3A) -> Vec3A {
     // Calculate the vectors representing two edges of the triangle
@@ -414,3 +446,93 @@ fn calculate_triangle_normal(v0: Vec3A, v1: Vec3A, v2: Vec3A) -> Vec3A {
     // Calculate the cross product of the two edges to get the normal
     edge1.cross(edge2).normalize()
 }
+
+// Accumulates the direction of increasing u and v over each triangle around its vertices,
+// then makes the tangents perpendicular to the normals
+fn generate_tangents(
+    positions: &[Vec3A],
+    normals: &[Vec3A],
+    uvs: &[Vec2],
+    indices: &[TriangleIndices],
+) -> Vec<Vec4> {
+    let mut u_directions = vec![Vec3A::ZERO; positions.len()];
+    let mut v_directions = vec![Vec3A::ZERO; positions.len()];
+
+    for TriangleIndices(a, b, c) in indices.iter() {
+        let [a, b, c] = [*a, *b, *c].map(|index| index as usize);
+
+        let (edge1, edge2) = (positions[b] - positions[a], positions[c] - positions[a]);
+        let (uv1, uv2) = (uvs[b] - uvs[a], uvs[c] - uvs[a]);
+
+        let determinant = uv1.x * uv2.y - uv2.x * uv1.y;
+        if determinant.abs() <= f32::EPSILON {
+            continue;
+        }
+
+        let u_direction = (edge1 * uv2.y - edge2 * uv1.y) / determinant;
+        let v_direction = (edge2 * uv1.x - edge1 * uv2.x) / determinant;
+
+        for vertex in [a, b, c] {
+            u_directions[vertex] += u_direction;
+            v_directions[vertex] += v_direction;
+        }
+    }
+
+    (0..positions.len())
+        .map(|vertex| {
+            let normal = normals[vertex];
+            let u_direction = u_directions[vertex];
+            let tangent = (u_direction - normal * normal.dot(u_direction)).normalize_or_zero();
+
+            // glTF texture coordinates start at the top left, so the normal
+            // map's up is towards decreasing v
+            let up = -v_directions[vertex];
+            let handedness = if normal.cross(tangent).dot(up) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+
+            tangent.extend(handedness)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_tangents_for_every_merged_mesh() {
+        // Two quads facing +z merged into one mesh,
+        // the first's u runs along +x and the second's along +y
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let positions = [corners, corners.map(|(x, y)| (x + 2.0, y))]
+            .concat()
+            .into_iter()
+            .map(|(x, y)| Vec3A::new(x, y, 0.0))
+            .collect::<Vec<_>>();
+        let normals = vec![Vec3A::Z; positions.len()];
+        let uvs = [
+            corners.map(|(x, y)| Vec2::new(x, 1.0 - y)),
+            corners.map(|(x, y)| Vec2::new(y, x)),
+        ]
+        .concat();
+        let indices = [
+            TriangleIndices(0, 1, 2),
+            TriangleIndices(0, 2, 3),
+            TriangleIndices(4, 5, 6),
+            TriangleIndices(4, 6, 7),
+        ];
+
+        let tangents = generate_tangents(&positions, &normals, &uvs, &indices);
+
+        assert_eq!(tangents.len(), positions.len());
+        for tangent in &tangents[..4] {
+            assert!(tangent.abs_diff_eq(Vec4::new(1.0, 0.0, 0.0, 1.0), 1e-5));
+        }
+        for tangent in &tangents[4..] {
+            assert!(tangent.abs_diff_eq(Vec4::new(0.0, 1.0, 0.0, 1.0), 1e-5));
+        }
+    }
+}
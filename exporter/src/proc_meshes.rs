@@ -1,4 +1,4 @@
-use shared::shapes;
+use shared::{mesh::AlphaMode, shapes};
 
 use crate::meshes::MeshOutput;
 
@@ -12,6 +12,7 @@ pub fn cube() -> MeshOutput {
             .flat_map(|p| Vec::from_iter(p.0))
             .collect(),
         attribute_count: 8,
+        alpha_mode: AlphaMode::Opaque,
     }
 }
 
@@ -25,6 +26,7 @@ pub fn triangle() -> MeshOutput {
             .flat_map(|p| Vec::from_iter(p.0))
             .collect(),
         attribute_count: 2,
+        alpha_mode: AlphaMode::Opaque,
     }
 }
 
@@ -38,5 +40,6 @@ pub fn plane() -> MeshOutput {
             .flat_map(|p| Vec::from_iter(p.0))
             .collect(),
         attribute_count: 2,
+        alpha_mode: AlphaMode::Opaque,
     }
 }
@@ -51,7 +51,7 @@ impl SkeletonOutput {
         let name = filename.to_uppercase();
 
         format!(
-            "pub const {name}: &SkeletonBytes<{bone_count}> = &SkeletonBytes(include_bytes!(\"{filename}\"));\n"
+            "pub const {name}: &SkeletonBytes<{bone_count}> = &SkeletonBytes(include_archive!(\"{filename}\"));\n"
         )
     }
 }
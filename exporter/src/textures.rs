@@ -1,26 +1,27 @@
 use crate::*;
-use bytemuck::from_bytes;
-use gltf::image::Data;
-use shared::texture::Texture;
+use bytemuck::pod_read_unaligned;
+use gltf::{image::Data, texture::WrappingMode, Document};
+use image::{imageops::FilterType, RgbaImage};
+use shared::texture::{MipLevel, Texture, WrapMode};
 
 pub struct TextureOutput {
     pub name: String,
     pub width: u32,
     pub height: u32,
     pub image_data: Vec<u8>,
+    pub wrap_u: WrapMode,
+    pub wrap_v: WrapMode,
 }
 
 impl TextureOutput {
     pub fn to_output(&self, config: &AssetList) -> String {
         // Write the struct as Rust code
         let filename = format!("{}_{TEXTURES_EXTENSION}", self.name);
-        let width = self.width;
-        let height = self.height;
 
         let out = Texture {
-            width: width as u16,
-            height: height as u16,
-            data: self.image_data.clone(),
+            wrap_u: self.wrap_u,
+            wrap_v: self.wrap_v,
+            mips: self.generate_mips(),
         };
 
         let archive = rkyv::to_bytes::<_, 256>(&out).unwrap();
@@ -28,9 +29,43 @@ impl TextureOutput {
         let name = filename.to_uppercase();
 
         format!(
-            "pub const {name}: &TextureBytes = &TextureBytes(include_bytes!(\"{filename}\"));\n"
+            "pub const {name}: &TextureBytes = &TextureBytes(include_archive!(\"{filename}\"));\n"
         )
     }
+
+    // Halves the image until it is a single pixel, filtering each level from the previous one
+    fn generate_mips(&self) -> Vec<MipLevel> {
+        let mut image = RgbaImage::from_raw(self.width, self.height, self.image_data.clone())
+            .expect("Image data doesn't match its size");
+        let mut mips = Vec::new();
+
+        loop {
+            let (width, height) = image.dimensions();
+            let next = (width > 1 || height > 1).then(|| {
+                let (width, height) = ((width / 2).max(1), (height / 2).max(1));
+                image::imageops::resize(&image, width, height, FilterType::Triangle)
+            });
+
+            mips.push(MipLevel {
+                width: width as u16,
+                height: height as u16,
+                data: image.into_raw(),
+            });
+
+            match next {
+                Some(next) => image = next,
+                None => break mips,
+            }
+        }
+    }
+}
+
+fn wrap_mode(mode: WrappingMode) -> WrapMode {
+    match mode {
+        WrappingMode::ClampToEdge => WrapMode::ClampToEdge,
+        WrappingMode::MirroredRepeat => WrapMode::MirroredRepeat,
+        WrappingMode::Repeat => WrapMode::Repeat,
+    }
 }
 
 pub fn generate_textures(config: &AssetList) -> String {
@@ -65,7 +100,7 @@ pub fn generate_textures(config: &AssetList) -> String {
         let image = image::load_from_memory(&bytes).unwrap();
         let image_data = image
             .pixels()
-            .flat_map(|(_x, _y, pixel)| [pixel.0[0], pixel.0[1], pixel.0[2]])
+            .flat_map(|(_x, _y, pixel)| pixel.0)
             .collect::<Vec<u8>>();
 
         let texture = TextureOutput {
@@ -73,6 +108,8 @@ pub fn generate_textures(config: &AssetList) -> String {
             width: image.width(),
             height: image.height(),
             image_data,
+            wrap_u: WrapMode::default(),
+            wrap_v: WrapMode::default(),
         };
 
         // Append the output String
@@ -85,12 +122,22 @@ pub fn generate_textures(config: &AssetList) -> String {
 }
 
 pub fn handle_glb_images(
+    document: &Document,
     images: Vec<Data>,
     output: &mut String,
     config: &AssetList,
     filename: &str,
 ) {
     for (index, image) in images.iter().enumerate() {
+        // Use the sampler of the first texture which refers to this image
+        let sampler = document
+            .textures()
+            .find(|texture| texture.source().index() == index)
+            .map(|texture| texture.sampler());
+        let (wrap_u, wrap_v) = sampler.map_or(Default::default(), |sampler| {
+            (wrap_mode(sampler.wrap_s()), wrap_mode(sampler.wrap_t()))
+        });
+
         let (size, alpha) = match image.format {
             gltf::image::Format::R8G8B8 => (1, false),
             gltf::image::Format::R8G8B8A8 => (1, true),
@@ -108,37 +155,26 @@ pub fn handle_glb_images(
             }
         };
 
-        let mut image_data = Vec::with_capacity((image.width * image.height) as usize);
+        let mut image_data = Vec::with_capacity((image.width * image.height) as usize * 4);
 
         let chunks = (3 * size) + if alpha { size } else { 0 };
 
         for pixel in image.pixels.chunks_exact(chunks) {
-            let (r, g, b) = match size {
-                1 => (pixel[0], pixel[1], pixel[2]),
-                2 => {
-                    let r = *from_bytes::<u16>(&pixel[0..2]) as f32 / u16::MAX as f32;
-                    let g = *from_bytes::<u16>(&pixel[2..4]) as f32 / u16::MAX as f32;
-                    let b = *from_bytes::<u16>(&pixel[4..6]) as f32 / u16::MAX as f32;
-
-                    let r = (r * u8::MAX as f32) / u8::MAX as f32;
-                    let g = (g * u8::MAX as f32) / u8::MAX as f32;
-                    let b = (b * u8::MAX as f32) / u8::MAX as f32;
-
-                    (r as u8, g as u8, b as u8)
+            // Converts a single channel into the 0 -> 255 range
+            let channel = |index: usize| {
+                let bytes = &pixel[index * size..(index + 1) * size];
+                match size {
+                    1 => bytes[0],
+                    2 => (pod_read_unaligned::<u16>(bytes) >> 8) as u8,
+                    4 => (pod_read_unaligned::<f32>(bytes).clamp(0.0, 1.0) * u8::MAX as f32) as u8,
+                    _ => unreachable!(),
                 }
-                4 => {
-                    let r = *from_bytes::<f32>(&pixel[0..4]) * u8::MAX as f32;
-                    let g = *from_bytes::<f32>(&pixel[4..8]) * u8::MAX as f32;
-                    let b = *from_bytes::<f32>(&pixel[8..12]) * u8::MAX as f32;
-
-                    (r as u8, g as u8, b as u8)
-                }
-                _ => unreachable!(),
             };
 
-            image_data.push(r);
-            image_data.push(g);
-            image_data.push(b);
+            // Images without alpha are fully opaque
+            let a = if alpha { channel(3) } else { u8::MAX };
+
+            image_data.extend([channel(0), channel(1), channel(2), a]);
         }
 
         let texture = TextureOutput {
@@ -146,6 +182,8 @@ pub fn handle_glb_images(
             width: image.width,
             height: image.height,
             image_data,
+            wrap_u,
+            wrap_v,
         };
 
         output.push_str(&texture.to_output(config));
@@ -0,0 +1,86 @@
+// Whole frames of the scenes built on the checked-in assets, at each resolution and tile size,
+// then with each rasterizer. Run with `cargo bench -p game`, criterion compares against the previous run
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use game::{camera::Camera, generated::textures, render_frame, scenes::*};
+use glam::Vec3A;
+use renderer::graphics::{Gpu, Rasterizer, Rgb8, Uniforms};
+
+// From the console's lowest resolution up to 720p
+const RESOLUTIONS: [(usize, usize); 4] = [(128, 72), (320, 180), (640, 360), (1280, 720)];
+
+// Around the default of 32 x 18, the largest don't divide the lowest resolutions
+const TILE_SIZES: [(usize, usize); 3] = [(16, 9), (32, 18), (64, 36)];
+
+fn scenes() -> [(&'static str, Box<dyn Scene<Rgb8>>); 4] {
+    [
+        ("fox", Box::new(FoxModelScene::new())),
+        ("damaged_helmet", Box::new(HelmetModelScene::new())),
+        ("mech", Box::new(MechScene::new())),
+        ("vs_background", Box::new(VsScene::new())),
+    ]
+}
+
+fn render_frames(c: &mut Criterion) {
+    for (name, scene) in scenes() {
+        let mut group = c.benchmark_group(name);
+
+        for (width, height) in RESOLUTIONS {
+            for (tile_width, tile_height) in TILE_SIZES {
+                let mut gpu = Gpu::new(
+                    width,
+                    height,
+                    Rgb8,
+                    Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+                )
+                .with_tile_size(tile_width, tile_height);
+                let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), width as f32 / height as f32);
+
+                let id = format!("{width}x{height}/tiles_{tile_width}x{tile_height}");
+                group.throughput(Throughput::Elements((width * height) as u64));
+                group.bench_function(BenchmarkId::from_parameter(id), |b| {
+                    b.iter(|| {
+                        render_frame(&mut gpu, &camera, scene.as_ref());
+                        black_box(gpu.generate_frame_buffer());
+                    })
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+// At the default tile size, the fox's many small triangles leave most stamps empty
+fn compare_rasterizers(c: &mut Criterion) {
+    for (name, scene) in scenes() {
+        let mut group = c.benchmark_group(format!("rasterizer/{name}"));
+
+        for (width, height) in RESOLUTIONS {
+            for rasterizer in [Rasterizer::Stamps, Rasterizer::Scanline] {
+                let mut gpu = Gpu::new(
+                    width,
+                    height,
+                    Rgb8,
+                    Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+                );
+                gpu.render_state.rasterizer = rasterizer;
+                let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), width as f32 / height as f32);
+
+                let id = format!("{width}x{height}/{rasterizer:?}");
+                group.throughput(Throughput::Elements((width * height) as u64));
+                group.bench_function(BenchmarkId::from_parameter(id), |b| {
+                    b.iter(|| {
+                        render_frame(&mut gpu, &camera, scene.as_ref());
+                        black_box(gpu.generate_frame_buffer());
+                    })
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, render_frames, compare_rasterizers);
+criterion_main!(benches);
@@ -0,0 +1,142 @@
+// Micro-benchmarks of the pipeline's hot loops, each in isolation.
+// Run with `cargo bench -p game --bench stages`
+
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup,
+    Criterion,
+};
+use game::generated::{meshes, textures};
+use glam::{Vec2, Vec4};
+use renderer::{
+    animation::Animator,
+    graphics::{
+        bench::{self, SetupTriangle},
+        DepthCompare, DepthConvention, DepthFormat, Gpu, Rgb8, Uniforms, ZBuffer,
+    },
+};
+use wide::f32x4;
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 180;
+// The renderer's default 32 x 18 tiles
+const TILE_PIXELS: usize = 32 * 18;
+
+fn edge_stepper(c: &mut Criterion) {
+    let mut group = c.benchmark_group("edge_stepper");
+
+    // Both are stepped across the same 32 x 18 tile at the origin
+    let partial = SetupTriangle::new([
+        Vec4::new(4.0, 2.0, 0.5, 1.0),
+        Vec4::new(28.0, 6.0, 0.5, 1.0),
+        Vec4::new(10.0, 16.0, 0.5, 1.0),
+    ]);
+    let covering = SetupTriangle::new([
+        Vec4::new(-64.0, -64.0, 0.5, 1.0),
+        Vec4::new(128.0, -64.0, 0.5, 1.0),
+        Vec4::new(-64.0, 128.0, 0.5, 1.0),
+    ]);
+
+    group.bench_function("partial_tile", |b| {
+        b.iter(|| bench::step_edges(black_box(&partial), Vec2::ZERO))
+    });
+    group.bench_function("covered_tile", |b| {
+        b.iter(|| bench::step_edges(black_box(&covering), Vec2::ZERO))
+    });
+    group.finish();
+}
+
+// One 4 pixel stamp after another across a tile, as the rasterizer tests them
+fn test_and_write<D: DepthFormat>(z_buffer: &mut ZBuffer<D>, depths: f32x4) -> f32 {
+    for pixel_index in (0..TILE_PIXELS).step_by(4) {
+        let passed = z_buffer.test(pixel_index, depths, 0b1111, DepthCompare::Nearer);
+        z_buffer.write(pixel_index, depths, passed);
+    }
+    z_buffer.farthest()
+}
+
+fn z_buffer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("z_buffer");
+    z_buffer_format::<f32>(&mut group, "f32");
+    z_buffer_format::<u16>(&mut group, "u16");
+    group.finish();
+}
+
+// Every stamp passes against a cleared buffer and is written, then fails against its own depths
+fn z_buffer_format<D: DepthFormat>(group: &mut BenchmarkGroup<WallTime>, name: &str) {
+    let depths = f32x4::new([0.5, 0.6, 0.7, 0.8]);
+    let cleared = || ZBuffer::<D>::new(TILE_PIXELS, DepthConvention::default());
+
+    group.bench_function(format!("{name}/write"), |b| {
+        b.iter_batched_ref(
+            cleared,
+            |z_buffer| test_and_write(z_buffer, black_box(depths)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut z_buffer = cleared();
+    test_and_write(&mut z_buffer, depths);
+    group.bench_function(format!("{name}/reject"), |b| {
+        b.iter(|| test_and_write(&mut z_buffer, black_box(depths)))
+    });
+}
+
+fn clipping(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clipping");
+    let mut gpu = Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+
+    // In clip space, from the common case of needing no clipping up to the worst
+    let triangles = [
+        (
+            "inside",
+            [
+                Vec4::new(-0.5, -0.5, 1.0, 2.0),
+                Vec4::new(0.5, -0.5, 1.0, 2.0),
+                Vec4::new(0.0, 0.5, 1.0, 2.0),
+            ],
+        ),
+        (
+            "near_plane",
+            [
+                Vec4::new(-0.5, -0.5, -1.0, 0.5),
+                Vec4::new(0.5, -0.5, 1.0, 2.0),
+                Vec4::new(0.0, 0.5, 1.0, 2.0),
+            ],
+        ),
+        (
+            "guard_band",
+            [
+                Vec4::new(-500.0, -500.0, 2.5, 5.0),
+                Vec4::new(500.0, -500.0, 2.5, 5.0),
+                Vec4::new(0.0, 500.0, 2.5, 5.0),
+            ],
+        ),
+    ];
+
+    for (name, positions) in triangles {
+        group.bench_function(name, |b| {
+            b.iter(|| bench::clip(&mut gpu, black_box(positions)))
+        });
+    }
+    group.finish();
+}
+
+fn animator(c: &mut Criterion) {
+    let mut animator = Animator::new(
+        meshes::FOX_SKL.as_skeleton(),
+        meshes::FOX_SKN.as_skin(),
+        meshes::FOX_RUN_ANM.as_anim(),
+    );
+
+    c.bench_function("animator_update_time", |b| {
+        b.iter(|| animator.update_time(black_box(1.0 / 60.0)))
+    });
+}
+
+criterion_group!(benches, edge_stepper, z_buffer, clipping, animator);
+criterion_main!(benches);
@@ -3,11 +3,11 @@ use std::f32::consts::{FRAC_PI_2, PI, TAU};
 use glam::{Mat4, Vec3, Vec3A};
 
 use gamercade_rs::prelude as gc;
+use renderer::graphics::NEAR_PLANE;
 
 use crate::math::Math;
 
 const PITCH_CLAMP: f32 = FRAC_PI_2 * 0.99;
-pub const NEAR_PLANE: f32 = 0.1;
 
 pub struct Camera {
     pub position: Vec3A,
@@ -17,6 +17,8 @@ pub struct Camera {
     pub movement_speed: f32,
     pub projection: Mat4,
     pub view: Mat4,
+    aspect_ratio: f32,
+    far_plane: Option<f32>,
 }
 
 impl Camera {
@@ -25,20 +27,32 @@ impl Camera {
         let sensitivity = 0.05;
         let movement_speed = 0.1;
 
-        let hfov = 103f32.to_radians();
-        let vfov = 2.0 * ((hfov / 2.0).tan() * aspect_ratio.recip()).atan();
-
         Camera {
             position,
             yaw: PI,
             pitch: 0.0,
             sensitivity,
             movement_speed,
-            projection: Mat4::perspective_infinite_rh(vfov, aspect_ratio, NEAR_PLANE),
+            projection: projection(aspect_ratio, None),
             view: Mat4::look_to_rh(position.into(), Vec3::NEG_Z, Vec3::Y),
+            aspect_ratio,
+            far_plane: None,
         }
     }
 
+    // Replaces the infinite projection with one which clips everything beyond the far plane
+    pub fn with_far_plane(mut self, far_plane: f32) -> Self {
+        self.far_plane = Some(far_plane);
+        self.projection = projection(self.aspect_ratio, self.far_plane);
+        self
+    }
+
+    // Rebuilds the projection for a resized screen, keeping the horizontal field of view
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
+        self.aspect_ratio = aspect_ratio;
+        self.projection = projection(aspect_ratio, self.far_plane);
+    }
+
     pub fn update(&mut self) {
         // TODO: Mouse movement
 
@@ -81,6 +95,17 @@ impl Camera {
     }
 }
 
+// A fixed horizontal field of view, infinite unless given a far plane
+fn projection(aspect_ratio: f32, far_plane: Option<f32>) -> Mat4 {
+    let hfov = 103f32.to_radians();
+    let vfov = 2.0 * ((hfov / 2.0).tan() * aspect_ratio.recip()).atan();
+
+    match far_plane {
+        Some(far_plane) => Mat4::perspective_rh(vfov, aspect_ratio, NEAR_PLANE, far_plane),
+        None => Mat4::perspective_infinite_rh(vfov, aspect_ratio, NEAR_PLANE),
+    }
+}
+
 fn forward_from_yaw_pitch(yaw: f32, pitch: f32) -> Vec3A {
     // Calculate the components of the forward vector
     let x = yaw.sin() * pitch.cos();
@@ -0,0 +1,58 @@
+use gamercade_rs::api::graphics_parameters::GraphicsParameters;
+#[cfg(target_arch = "wasm32")]
+use gamercade_rs::prelude as gc;
+use renderer::graphics::{RenderTarget, R5G5B4};
+use shared::types::Color;
+
+/// Outputs to the Gamercade console, using the 5-bit red / 5-bit green /
+/// 4-bit blue palette layout from `5r5g4b_color.gce`
+#[derive(Clone, Copy, Default)]
+pub struct ConsoleTarget;
+
+impl RenderTarget for ConsoleTarget {
+    type Pixel = GraphicsParameters;
+
+    fn encode(&self, color: Color) -> Self::Pixel {
+        let pixel = R5G5B4.encode(color);
+
+        GraphicsParameters::default()
+            .palette_index(R5G5B4::palette_index(pixel))
+            .color_index(R5G5B4::color_index(pixel))
+    }
+
+    fn channel_levels(&self) -> [u16; 3] {
+        R5G5B4.channel_levels()
+    }
+}
+
+// Scenes also run headless in the golden image tests, where there is no
+// console to poll, so they should read their input through these
+#[cfg(target_arch = "wasm32")]
+pub fn right_stick_pressed() -> bool {
+    gc::button_right_stick_pressed(0) == Some(true)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn left_stick_pressed() -> bool {
+    gc::button_left_stick_pressed(0) == Some(true)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn left_shoulder_pressed() -> bool {
+    gc::button_left_shoulder_pressed(0) == Some(true)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn right_stick_pressed() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn left_stick_pressed() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn left_shoulder_pressed() -> bool {
+    false
+}
@@ -0,0 +1,46 @@
+use glam::{Vec3A, Vec4Swizzles};
+use renderer::{
+    animation::Animator,
+    graphics::{DepthFormat, Gpu, PrimitiveVertex, RenderState, RenderTarget},
+    shaders::{BaseVertexShader, ColorBlend},
+};
+
+const BONE_COLOR: [f32; 3] = [0.0, 1.0, 0.0];
+const ROOT_COLOR: [f32; 3] = [1.0, 0.0, 0.0];
+
+/// Draws the skeleton of the animator, with a line from each bone to its parent and a point
+/// at each root. Drawn over everything else, as the skeleton is inside of the mesh
+pub fn render_animator<T: RenderTarget, D: DepthFormat, const B: usize, const I: usize>(
+    gpu: &mut Gpu<T, D>,
+    animator: &Animator<B, I>,
+) {
+    let vertex = |bone: usize, color| {
+        let position = animator.current_pose[bone].w_axis.xyz();
+        PrimitiveVertex::new(Vec3A::from(position), color)
+    };
+
+    let mut bones = Vec::new();
+    let mut roots = Vec::new();
+    for (bone_index, bone) in animator.skeleton.0.iter().enumerate() {
+        if bone.parent_index.is_negative() {
+            roots.push(vertex(bone_index, ROOT_COLOR));
+        } else {
+            let parent_index = bone.parent_index as usize;
+            bones.push([
+                vertex(bone_index, BONE_COLOR),
+                vertex(parent_index, BONE_COLOR),
+            ]);
+        }
+    }
+
+    let render_state = gpu.render_state;
+    gpu.render_state = RenderState {
+        point_size: 3.0,
+        ..RenderState::overlay()
+    };
+
+    gpu.render_lines(&bones, BaseVertexShader, ColorBlend);
+    gpu.render_points(&roots, BaseVertexShader, ColorBlend);
+
+    gpu.render_state = render_state;
+}
@@ -2,26 +2,27 @@ use std::mem::MaybeUninit;
 
 use camera::Camera;
 
+use console::ConsoleTarget;
 use gamercade_rs::prelude as gc;
+use generated::textures;
 use glam::Vec3A;
-use graphics::Gpu;
+use renderer::graphics::{Dither, Gpu, Light, Lights, RenderTarget, Uniforms, MAX_LIGHTS};
 use scenes::*;
 
 mod actor;
-mod animation;
-mod camera;
+pub mod camera;
+mod console;
+pub mod debug;
 pub mod generated;
-mod graphics;
 mod math;
-mod scenes;
-mod shaders;
+pub mod scenes;
 
 static mut GAME_STATE: MaybeUninit<GameState> = MaybeUninit::uninit();
 static mut CAMERA: MaybeUninit<Camera> = MaybeUninit::uninit();
-static mut GPU: MaybeUninit<Gpu> = MaybeUninit::uninit();
+static mut GPU: MaybeUninit<Gpu<ConsoleTarget>> = MaybeUninit::uninit();
 
 pub struct GameState {
-    scenes: Vec<Box<dyn Scene>>,
+    scenes: Vec<Box<dyn Scene<ConsoleTarget>>>,
     scene_index: usize,
 }
 static mut DATA_PTR: *const u8 = std::ptr::null();
@@ -51,7 +52,7 @@ pub unsafe extern "C" fn init() {
         .unwrap();
     gc::console_log(&format!("{text}"));
 
-    let scenes: Vec<Box<dyn Scene>> = vec![
+    let scenes: Vec<Box<dyn Scene<ConsoleTarget>>> = vec![
         (Box::new(VsScene::new())),
         (Box::new(MultimeshScene::new())),
         (Box::new(MechScene::new())),
@@ -72,7 +73,15 @@ pub unsafe extern "C" fn init() {
         Vec3A::new(0.0, 0.0, 5.0),
         screen_width as f32 / screen_height as f32,
     ));
-    GPU.write(Gpu::new(screen_width, screen_height));
+    let gpu = GPU.write(Gpu::new(
+        screen_width,
+        screen_height,
+        ConsoleTarget,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    ));
+
+    // Hides the banding of lit gradients in the console's palette
+    gpu.dither = Dither::Bayer4;
 }
 
 /// # Safety
@@ -103,20 +112,40 @@ pub unsafe extern "C" fn update() {
 pub unsafe extern "C" fn draw() {
     let gpu = GPU.assume_init_mut();
     let game_state = GAME_STATE.assume_init_ref();
+    let camera = CAMERA.assume_init_mut();
+    let scene = game_state.scenes[game_state.scene_index].as_ref();
+
+    let screen_size = (gc::width(), gc::height());
+    if gpu.screen_size() != screen_size {
+        gpu.resize(screen_size.0, screen_size.1);
+        camera.set_aspect_ratio(gpu.aspect_ratio());
+    }
+    if gpu.render_scale() != scene.render_scale() {
+        gpu.set_render_scale(scene.render_scale());
+    }
 
+    render_frame(gpu, camera, scene);
+
+    gc::write_pixel_buffer(0, gpu.generate_frame_buffer());
+}
+
+/// Clears the buffers, sets up the per frame uniforms from the camera and draws the scene.
+/// The frame is left in the tiles, ready for [`Gpu::generate_frame_buffer`]
+pub fn render_frame<T: RenderTarget>(gpu: &mut Gpu<T>, camera: &Camera, scene: &dyn Scene<T>) {
     // Clear all of the buffers
     gpu.reset_frame();
 
     // For Calculating MVP Later
-    let camera = CAMERA.assume_init_ref();
     gpu.uniforms.projection = camera.projection;
     gpu.uniforms.view = camera.view;
+    gpu.uniforms.camera_position = camera.position;
 
-    gpu.uniforms.light_position = camera.position;
-    gpu.uniforms.light_intensity = 1.05;
+    // A light at the camera, scenes can replace it with their own
+    gpu.uniforms.lights =
+        Lights::from([Light::point(camera.position, f32::INFINITY).with_intensity(1.05)]);
+    gpu.uniforms.light_limit = MAX_LIGHTS;
     gpu.uniforms.ambient_light = 0.25;
+    gpu.uniforms.shadow_map = None;
 
-    game_state.scenes[game_state.scene_index].draw(gpu);
-
-    gc::write_pixel_buffer(0, gpu.generate_frame_buffer());
+    scene.draw(gpu);
 }
@@ -1,12 +1,12 @@
 use glam::Mat4;
-
-use crate::{
-    actor::Actor,
+use renderer::{
     animation::Animator,
-    generated::meshes,
+    graphics::{Gpu, RenderTarget},
     shaders::{Animated, TexturedLit},
 };
 
+use crate::{actor::Actor, generated::meshes};
+
 use super::Scene;
 
 pub struct BlockbenchScene {
@@ -35,8 +35,8 @@ impl BlockbenchScene {
     }
 }
 
-impl Scene for BlockbenchScene {
-    fn draw(&self, gpu: &mut crate::graphics::Gpu) {
+impl<T: RenderTarget> Scene<T> for BlockbenchScene {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.mesh.transform;
         gpu.uniforms.diffuse = meshes::BLOCKBENCH_0_TEX.as_texture();
         gpu.render_mesh(self.mesh.mesh, self.shader, TexturedLit);
@@ -1,11 +1,13 @@
 use glam::Mat4;
+use renderer::{
+    graphics::{Gpu, RenderTarget},
+    shaders::{BaseVertexShader, TexturedNormalMapLit},
+};
 
 use super::Scene;
 use crate::{
     actor::Actor,
     generated::{meshes, textures},
-    graphics::Gpu,
-    shaders::{BaseVertexShader, TexturedNormalMapLit},
 };
 
 pub struct CubeScene {
@@ -24,12 +26,13 @@ impl CubeScene {
     }
 }
 
-impl Scene for CubeScene {
+impl<T: RenderTarget> Scene<T> for CubeScene {
     fn update(&mut self) {}
 
-    fn draw(&self, gpu: &mut Gpu) {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.cube.transform;
         gpu.uniforms.diffuse = textures::BRICKWALL_TEX.as_texture();
+        gpu.uniforms.normal = textures::BRICKWALL_NORMAL_TEX.as_texture();
         gpu.render_mesh(self.cube.mesh, BaseVertexShader, TexturedNormalMapLit);
     }
 }
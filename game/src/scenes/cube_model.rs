@@ -1,11 +1,11 @@
 use glam::Mat4;
-
-use crate::{
-    actor::Actor,
-    generated::meshes,
+use renderer::{
+    graphics::{Gpu, RenderTarget},
     shaders::{BaseVertexShader, ColorBlendLit},
 };
 
+use crate::{actor::Actor, generated::meshes};
+
 use super::Scene;
 
 pub struct CubeModelScene {
@@ -24,8 +24,8 @@ impl CubeModelScene {
     }
 }
 
-impl Scene for CubeModelScene {
-    fn draw(&self, gpu: &mut crate::graphics::Gpu) {
+impl<T: RenderTarget> Scene<T> for CubeModelScene {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.cube.transform;
         gpu.render_mesh(self.cube.mesh, BaseVertexShader, ColorBlendLit);
     }
@@ -1,13 +1,15 @@
 use std::f32::consts::FRAC_2_PI;
 
 use glam::{Mat4, Vec3A};
+use renderer::{
+    graphics::{Gpu, RenderTarget},
+    shaders::{BaseVertexShader, TexturedNormalMapLit},
+};
 
 use super::Scene;
 use crate::{
     actor::Actor,
     generated::{meshes, textures},
-    graphics::Gpu,
-    shaders::{BaseVertexShader, TexturedNormalMapLit},
 };
 
 pub struct CubesScene {
@@ -40,12 +42,12 @@ impl CubesScene {
     }
 }
 
-impl Scene for CubesScene {
+impl<T: RenderTarget> Scene<T> for CubesScene {
     fn update(&mut self) {
         self.cubes.iter_mut().for_each(|a| a.update());
     }
 
-    fn draw(&self, gpu: &mut Gpu) {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         self.cubes.iter().for_each(|cube| {
             gpu.uniforms.model = cube.transform;
             gpu.uniforms.diffuse = textures::BRICKWALL_TEX.as_texture();
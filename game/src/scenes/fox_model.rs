@@ -1,27 +1,33 @@
-use gamercade_rs::prelude as gc;
-use glam::{Mat4, Vec3};
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3, Vec3A};
+use renderer::{
+    animation::Animator,
+    graphics::{Gpu, Light, Lights, RenderTarget},
+    shaders::{Animated, BaseVertexShader, DepthOnly, TexturedLit, TexturedNormalMapLit},
+};
 use shared::animation::ArchivedAnimation;
 
 use crate::{
     actor::Actor,
-    animation::Animator,
-    generated::meshes,
-    shaders::{Animated, TexturedLit},
+    console, debug,
+    generated::{meshes, textures},
 };
 
-use super::Scene;
+use super::{directional_shadow, Scene};
 
 pub struct FoxModelScene {
     fox: Actor<5>,
+    floor: Actor<8>,
     shader: Animated<24, 4>,
     anim_index: usize,
     anims: [&'static ArchivedAnimation; 3],
+    // Toggled with the left shoulder button
+    show_skeleton: bool,
 }
 
 impl FoxModelScene {
     pub fn new() -> Self {
-        gc::console_log("Initialize FOX SCENE:");
-
         let anims = [
             meshes::FOX_SURVEY_ANM.as_anim(),
             meshes::FOX_WALK_ANM.as_anim(),
@@ -39,28 +45,66 @@ impl FoxModelScene {
         Self {
             fox: Actor {
                 mesh: meshes::FOX.as_mesh(),
-                transform: Mat4::from_scale(Vec3::splat(0.03)),
+                transform: Mat4::from_translation(Vec3::new(0.0, -2.0, 0.0))
+                    * Mat4::from_scale(Vec3::splat(0.03)),
+                delta: 0.0,
+            },
+            // A flattened cube, with its top face where the fox stands
+            floor: Actor {
+                mesh: meshes::CUBE.as_mesh(),
+                transform: Mat4::from_translation(Vec3::new(0.0, -2.25, 0.0))
+                    * Mat4::from_scale(Vec3::new(6.0, 0.25, 6.0)),
                 delta: 0.0,
             },
             shader,
             anim_index: 0,
             anims,
+            show_skeleton: false,
         }
     }
 }
 
-impl Scene for FoxModelScene {
-    fn draw(&self, gpu: &mut crate::graphics::Gpu) {
+impl<T: RenderTarget> Scene<T> for FoxModelScene {
+    fn draw(&self, gpu: &mut Gpu<T>) {
+        let key_direction = Vec3A::new(-0.5, -1.0, -0.7);
+
+        // Only the fox casts a shadow, the floor has nothing to shadow it
         gpu.uniforms.model = self.fox.transform;
-        gpu.uniforms.diffuse = meshes::FOX_0_TEX.as_texture();
+        let (view, projection) =
+            directional_shadow(gpu, key_direction, Vec3::new(0.0, -1.0, 0.0), 3.0);
+        let shadow_map = gpu.render_shadow_map(view, projection, |gpu| {
+            gpu.render_mesh(self.fox.mesh, self.shader, DepthOnly)
+        });
+        gpu.uniforms.shadow_map = Some(Arc::new(shadow_map));
+
+        // A key light from above, with a dim fill from the camera
+        gpu.uniforms.lights = Lights::from([
+            Light::directional(key_direction),
+            Light::point(gpu.uniforms.camera_position, f32::INFINITY).with_intensity(0.35),
+        ]);
 
+        gpu.uniforms.diffuse = meshes::FOX_0_TEX.as_texture();
         gpu.render_mesh(self.fox.mesh, self.shader, TexturedLit);
+
+        gpu.uniforms.model = self.floor.transform;
+        gpu.uniforms.diffuse = textures::BRICKWALL_TEX.as_texture();
+        gpu.uniforms.normal = textures::BRICKWALL_NORMAL_TEX.as_texture();
+        gpu.render_mesh(self.floor.mesh, BaseVertexShader, TexturedNormalMapLit);
+
+        if self.show_skeleton {
+            gpu.uniforms.model = self.fox.transform;
+            debug::render_animator(gpu, &self.shader.animator);
+        }
     }
 
     fn update(&mut self) {
         self.shader.animator.update_time(0.016);
 
-        if let Some(true) = gc::button_right_stick_pressed(0) {
+        if console::left_shoulder_pressed() {
+            self.show_skeleton = !self.show_skeleton;
+        }
+
+        if console::right_stick_pressed() {
             self.anim_index += 1;
 
             if self.anim_index == self.anims.len() {
@@ -68,7 +112,7 @@ impl Scene for FoxModelScene {
             }
 
             self.shader.animator.animation = self.anims[self.anim_index];
-        } else if let Some(true) = gc::button_left_stick_pressed(0) {
+        } else if console::left_stick_pressed() {
             self.anim_index -= 1;
 
             if self.anim_index == usize::MAX {
@@ -1,17 +1,17 @@
 use std::f32::consts::FRAC_PI_2;
 
 use glam::{Mat4, Vec3};
-
-use crate::{
-    actor::Actor,
-    generated::meshes,
-    shaders::{BaseVertexShader, HelmetShader},
+use renderer::{
+    graphics::{Gpu, RenderTarget},
+    shaders::{PbrShader, PbrVertexShader},
 };
 
+use crate::{actor::Actor, generated::meshes};
+
 use super::Scene;
 
 pub struct HelmetModelScene {
-    helmet: Actor<5>,
+    helmet: Actor<9>,
 }
 
 impl HelmetModelScene {
@@ -26,12 +26,20 @@ impl HelmetModelScene {
     }
 }
 
-impl Scene for HelmetModelScene {
-    fn draw(&self, gpu: &mut crate::graphics::Gpu) {
+impl<T: RenderTarget> Scene<T> for HelmetModelScene {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.helmet.transform;
         gpu.uniforms.diffuse = meshes::DAMAGEDHELMET_0_TEX.as_texture();
         gpu.uniforms.normal = meshes::DAMAGEDHELMET_4_TEX.as_texture();
-        gpu.render_mesh(self.helmet.mesh, BaseVertexShader, HelmetShader);
+        gpu.uniforms.emissive = meshes::DAMAGEDHELMET_2_TEX.as_texture();
+        gpu.uniforms.occlusion = meshes::DAMAGEDHELMET_3_TEX.as_texture();
+        gpu.uniforms.metallic_roughness = meshes::DAMAGEDHELMET_1_TEX.as_texture();
+        gpu.render_mesh(self.helmet.mesh, PbrVertexShader, PbrShader);
+    }
+
+    // Every pixel runs the full PBR shader with 5 textures
+    fn render_scale(&self) -> f32 {
+        0.75
     }
 
     fn update(&mut self) {
@@ -1,4 +1,8 @@
-use crate::Gpu;
+// Scenes are always constructed explicitly, a Default would be misleading
+#![allow(clippy::new_without_default)]
+
+use glam::{Mat4, Vec3, Vec3A};
+use renderer::graphics::{Gpu, RenderTarget, NEAR_PLANE};
 
 mod cubes;
 pub use cubes::CubesScene;
@@ -30,7 +34,31 @@ pub use multimesh::MultimeshScene;
 mod helmet_model;
 pub use helmet_model::HelmetModelScene;
 
-pub trait Scene {
+pub trait Scene<T: RenderTarget> {
     fn update(&mut self);
-    fn draw(&self, gpu: &mut Gpu);
+    fn draw(&self, gpu: &mut Gpu<T>);
+
+    // Heavy scenes render below the screen's resolution to hold the framerate
+    fn render_scale(&self) -> f32 {
+        1.0
+    }
+}
+
+// View and projection for a directional light's shadow map covering a sphere around the target.
+// Shadow maps need a perspective projection, so the light looks from far enough away to be
+// close to parallel
+fn directional_shadow<T: RenderTarget>(
+    gpu: &Gpu<T>,
+    direction: Vec3A,
+    target: Vec3,
+    radius: f32,
+) -> (Mat4, Mat4) {
+    let distance = radius * 4.0;
+    let eye = target - Vec3::from(direction.normalize()) * distance;
+    let vfov = 2.0 * (radius / distance).atan();
+
+    (
+        Mat4::look_at_rh(eye, target, Vec3::Y),
+        Mat4::perspective_rh(vfov, gpu.aspect_ratio(), NEAR_PLANE, distance * 2.0),
+    )
 }
@@ -1,10 +1,13 @@
 use glam::Mat4;
+use renderer::{
+    animation::Animator,
+    graphics::{Gpu, RenderTarget},
+    shaders::{Animated, TexturedLit},
+};
 
 use crate::{
     actor::Actor,
-    animation::Animator,
     generated::{meshes, textures},
-    shaders::{Animated, TexturedLit},
 };
 
 use super::Scene;
@@ -35,8 +38,8 @@ impl MultimeshScene {
     }
 }
 
-impl Scene for MultimeshScene {
-    fn draw(&self, gpu: &mut crate::graphics::Gpu) {
+impl<T: RenderTarget> Scene<T> for MultimeshScene {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.mesh.transform;
         gpu.uniforms.diffuse = textures::ENDESGA32_TEX.as_texture();
         gpu.render_mesh(self.mesh.mesh, self.shader, TexturedLit);
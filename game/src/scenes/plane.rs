@@ -1,10 +1,12 @@
 use glam::Mat4;
+use renderer::{
+    graphics::{Gpu, RenderTarget},
+    shaders::{BaseVertexShader, Textured},
+};
 
 use crate::{
     actor::Actor,
     generated::{meshes, textures},
-    graphics::Gpu,
-    shaders::{BaseVertexShader, Textured},
 };
 
 use super::Scene;
@@ -13,11 +15,11 @@ pub struct PlaneScene {
     plane: Actor<2>,
 }
 
-impl Scene for PlaneScene {
+impl<T: RenderTarget> Scene<T> for PlaneScene {
     fn update(&mut self) { // Do nothing
     }
 
-    fn draw(&self, gpu: &mut Gpu) {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.plane.transform;
         gpu.uniforms.diffuse = textures::GAMERCADE_TEX.as_texture();
         gpu.render_mesh(self.plane.mesh, BaseVertexShader, Textured);
@@ -1,10 +1,12 @@
 use glam::Mat4;
+use renderer::{
+    graphics::{Gpu, RenderTarget},
+    shaders::{BaseVertexShader, Textured},
+};
 
 use crate::{
     actor::Actor,
     generated::{meshes, textures},
-    graphics::Gpu,
-    shaders::{BaseVertexShader, Textured},
 };
 
 use super::Scene;
@@ -13,12 +15,12 @@ pub struct TriangleScene {
     triangle: Actor<2>,
 }
 
-impl Scene for TriangleScene {
+impl<T: RenderTarget> Scene<T> for TriangleScene {
     fn update(&mut self) {
         // Do nothing
     }
 
-    fn draw(&self, gpu: &mut Gpu) {
+    fn draw(&self, gpu: &mut Gpu<T>) {
         gpu.uniforms.model = self.triangle.transform;
         gpu.uniforms.diffuse = textures::GAMERCADE_TEX.as_texture();
         gpu.render_mesh(self.triangle.mesh, BaseVertexShader, Textured);
@@ -1,14 +1,16 @@
-use glam::{Mat4, Quat, Vec3};
-use shared::{mesh::ArchivedMesh, texture::ArchivedTexture};
+use std::sync::Arc;
 
-use crate::{
-    actor::Actor,
+use glam::{Mat4, Quat, Vec3, Vec3A};
+use renderer::{
     animation::Animator,
-    generated::meshes,
-    shaders::{Animated, BaseVertexShader, TexturedLit},
+    graphics::{Gpu, Light, Lights, RenderTarget},
+    shaders::{Animated, BaseVertexShader, DepthOnly, PixelShader, TexturedLit},
 };
+use shared::{mesh::ArchivedMesh, texture::ArchivedTexture};
 
-use super::Scene;
+use crate::{actor::Actor, generated::meshes};
+
+use super::{directional_shadow, Scene};
 
 pub struct VsScene {
     background: &'static ArchivedMesh<5>,
@@ -68,19 +70,49 @@ impl VsScene {
             background,
         }
     }
-}
 
-impl Scene for VsScene {
-    fn draw(&self, gpu: &mut crate::graphics::Gpu) {
-        // Characters first
-        gpu.uniforms.diffuse = crate::generated::textures::TESTCHARTEXTURE_TEX.as_texture();
+    fn draw_characters<T: RenderTarget>(&self, gpu: &mut Gpu<T>, ps: impl PixelShader<8>) {
         gpu.uniforms.model = self.mesh_1.transform;
-        gpu.render_mesh(self.mesh_1.mesh, self.shader_1, TexturedLit);
+        gpu.render_mesh(self.mesh_1.mesh, self.shader_1, ps);
 
         gpu.uniforms.model = self.mesh_2.transform;
-        gpu.render_mesh(self.mesh_2.mesh, self.shader_2, TexturedLit);
+        gpu.render_mesh(self.mesh_2.mesh, self.shader_2, ps);
+    }
+}
+
+impl<T: RenderTarget> Scene<T> for VsScene {
+    fn draw(&self, gpu: &mut Gpu<T>) {
+        let key_direction = Vec3A::new(-0.4, -0.6, -1.0);
+
+        // The characters cast shadows from the key light onto themselves and the floor
+        let (view, projection) =
+            directional_shadow(gpu, key_direction, Vec3::new(0.0, 0.0, -1.0), 7.0);
+        let shadow_map =
+            gpu.render_shadow_map(view, projection, |gpu| self.draw_characters(gpu, DepthOnly));
+        gpu.uniforms.shadow_map = Some(Arc::new(shadow_map));
+
+        // A warm key light, a cool rim light from behind, and colored fills on either side
+        gpu.uniforms.lights = Lights::from([
+            Light::directional(key_direction)
+                .with_color(Vec3A::new(1.0, 0.92, 0.8))
+                .with_intensity(0.9),
+            Light::directional(Vec3A::new(0.2, -0.3, 1.0))
+                .with_color(Vec3A::new(0.6, 0.75, 1.0))
+                .with_intensity(0.6),
+            Light::point(Vec3A::new(-6.0, 1.0, 3.0), 8.0)
+                .with_color(Vec3A::new(1.0, 0.3, 0.2))
+                .with_intensity(0.5),
+            Light::point(Vec3A::new(6.0, 1.0, 3.0), 8.0)
+                .with_color(Vec3A::new(0.2, 0.4, 1.0))
+                .with_intensity(0.5),
+        ]);
+
+        // Characters first
+        gpu.uniforms.diffuse = crate::generated::textures::TESTCHARTEXTURE_TEX.as_texture();
+        self.draw_characters(gpu, TexturedLit);
 
-        // Then Background
+        // Then Background, which is only lit by the key light
+        gpu.uniforms.light_limit = 1;
         gpu.uniforms.diffuse = self.bg_texture;
         gpu.uniforms.model = Mat4::from_translation(Vec3::new(0.0, -1.0, 0.0));
         gpu.render_mesh(self.background, BaseVertexShader, TexturedLit);
@@ -0,0 +1,114 @@
+// Meshes are drawn with the alpha mode exported from their glTF material,
+// unless the draw picks another blend mode
+
+use game::generated::textures;
+use glam::{Mat4, Vec3A, Vec4};
+use renderer::{
+    graphics::{BlendMode, Gpu, Rgb8, Uniforms},
+    shaders::{BaseVertexShader, Derivatives, PixelShader},
+};
+use shared::{
+    mesh::{AlphaMode, Mesh},
+    types::Color,
+    vertex_parameters::VertexParametersList,
+    IndexList, TriangleIndices, VertexList,
+};
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 36;
+
+// Red and green from the first two parameters, alpha from the third
+#[derive(Clone, Copy)]
+struct Translucent;
+
+impl PixelShader<3> for Translucent {
+    fn run(_: &Uniforms, [r, g, a]: [f32; 3], _: &Derivatives<3>) -> Color {
+        Color::from([r, g, 0.0, a])
+    }
+}
+
+// Covers the whole screen in clip space, with the parameters of its left and right edges
+fn quad(left: [f32; 3], right: [f32; 3], alpha_mode: AlphaMode) -> rkyv::AlignedVec {
+    let vertices =
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(x, y)| Vec3A::new(x, y, 0.5));
+    let parameters = [left, right, right, left].concat();
+
+    let mesh = Mesh::<3> {
+        vertices: VertexList(Box::new(vertices)),
+        indices: IndexList(Box::new([
+            TriangleIndices(0, 1, 2),
+            TriangleIndices(0, 2, 3),
+        ])),
+        parameters: VertexParametersList::from_flat_slice(&parameters),
+        alpha_mode,
+    };
+    rkyv::to_bytes::<_, 256>(&mesh).unwrap()
+}
+
+// Quads are drawn at w = 1 unless moved in front, where w = 0.5
+fn draw(gpu: &mut Gpu<Rgb8>, quad: &rkyv::AlignedVec, in_front: bool) {
+    gpu.uniforms.model = match in_front {
+        true => Mat4::from_diagonal(Vec4::splat(0.5)),
+        false => Mat4::IDENTITY,
+    };
+    let mesh = unsafe { rkyv::archived_root::<Mesh<3>>(quad) };
+    gpu.render_mesh(mesh, BaseVertexShader, Translucent);
+}
+
+fn gpu() -> Gpu<Rgb8> {
+    Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    )
+}
+
+fn pixel(frame: &[[u8; 3]], x: usize) -> [u8; 3] {
+    frame[HEIGHT / 2 * WIDTH + x]
+}
+
+// Parameters are interpolated, so channels can be off by one
+fn is_color(pixel: [u8; 3], color: [u8; 3]) -> bool {
+    pixel.iter().zip(color).all(|(p, c)| p.abs_diff(c) <= 1)
+}
+
+#[test]
+fn masked_materials_discard_below_the_cutoff() {
+    let mut gpu = gpu();
+    let masked = quad([1.0, 0.0, 0.0], [1.0, 0.0, 1.0], AlphaMode::Mask(0.5));
+    let background = quad([0.0, 1.0, 1.0], [0.0, 1.0, 1.0], AlphaMode::Opaque);
+
+    // Alpha rises from left to right, discarding the left half
+    draw(&mut gpu, &masked, true);
+    let frame = gpu.generate_frame_buffer();
+    assert_eq!(pixel(frame, 4), [0, 0, 0]);
+    assert!(is_color(pixel(frame, WIDTH - 4), [255, 0, 0]));
+
+    // Only the fragments which weren't discarded wrote their depth,
+    // so the background behind shows through the discarded half
+    draw(&mut gpu, &background, false);
+    let frame = gpu.generate_frame_buffer();
+    assert!(is_color(pixel(frame, 4), [0, 255, 0]));
+    assert!(is_color(pixel(frame, WIDTH - 4), [255, 0, 0]));
+}
+
+#[test]
+fn blended_materials_mix_with_the_frame() {
+    let mut gpu = gpu();
+    let background = quad([0.0, 1.0, 1.0], [0.0, 1.0, 1.0], AlphaMode::Opaque);
+    let blended = quad([1.0, 0.0, 0.5], [1.0, 0.0, 0.5], AlphaMode::Blend);
+
+    draw(&mut gpu, &background, false);
+    draw(&mut gpu, &blended, true);
+    let frame = gpu.generate_frame_buffer();
+    assert!(is_color(pixel(frame, WIDTH / 2), [128, 127, 0]));
+
+    // An explicit blend mode replaces the material's
+    gpu.reset_frame();
+    draw(&mut gpu, &background, false);
+    gpu.blend_mode = BlendMode::Additive;
+    draw(&mut gpu, &blended, true);
+    let frame = gpu.generate_frame_buffer();
+    assert!(is_color(pixel(frame, WIDTH / 2), [128, 255, 0]));
+}
@@ -0,0 +1,81 @@
+// Each debug view replaces the frame with a view of how it was rendered,
+// checked against what is known about the scene
+
+use game::{
+    camera::Camera,
+    generated::textures,
+    render_frame,
+    scenes::{CubeScene, VsScene},
+};
+use glam::Vec3A;
+use renderer::graphics::{DebugView, Gpu, Rgb8, Uniforms};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 180;
+
+fn render(debug_view: DebugView, scene: &dyn game::scenes::Scene<Rgb8>) -> Vec<[u8; 3]> {
+    let mut gpu = Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), WIDTH as f32 / HEIGHT as f32);
+
+    gpu.debug_view = debug_view;
+    render_frame(&mut gpu, &camera, scene);
+    gpu.generate_frame_buffer().to_vec()
+}
+
+#[test]
+fn overdraw_shows_where_pixels_were_drawn_more_than_once() {
+    let frame = render(DebugView::Overdraw, &VsScene::new());
+
+    // Pixels drawn once are pure blue, the background over and over again
+    let blue = frame.iter().filter(|pixel| **pixel == [0, 0, 255]).count();
+    let more = frame
+        .iter()
+        .filter(|pixel| pixel[0] > 0 || pixel[1] > 0)
+        .count();
+    assert!(blue > 0 && more > 0, "{blue} {more}");
+}
+
+#[test]
+fn depth_is_brighter_nearer_the_camera() {
+    let frame = render(DebugView::Depth, &CubeScene::new());
+
+    // The cube is the only thing drawn, the rest stays black
+    let drawn = frame.iter().filter(|pixel| **pixel != [0; 3]);
+    assert!(drawn
+        .clone()
+        .all(|pixel| pixel[0] == pixel[1] && pixel[1] == pixel[2]));
+    assert!(drawn.clone().any(|pixel| pixel[0] == 255));
+    assert!(drawn.count() < WIDTH * HEIGHT);
+}
+
+#[test]
+fn tile_views_fill_whole_tiles() {
+    for debug_view in [DebugView::TileTriangles, DebugView::TileAccept] {
+        let frame = render(debug_view, &CubeScene::new());
+
+        // Tiles are 32x18, each one's inside is a single color
+        for tile_y in 0..HEIGHT / 18 {
+            for tile_x in 0..WIDTH / 32 {
+                let pixel = |x, y| frame[(tile_y * 18 + y) * WIDTH + tile_x * 32 + x];
+                let fill = pixel(1, 1);
+                assert!((1..31).all(|x| (1..17).all(|y| pixel(x, y) == fill)));
+            }
+        }
+        assert!(frame.iter().any(|pixel| *pixel != [0; 3]), "{debug_view:?}");
+    }
+}
+
+#[test]
+fn normals_replace_the_shaded_colors() {
+    let shaded = render(DebugView::None, &CubeScene::new());
+    let normals = render(DebugView::Normals, &CubeScene::new());
+
+    let drawn = |frame: &[[u8; 3]]| frame.iter().filter(|pixel| **pixel != [0; 3]).count();
+    assert_ne!(shaded, normals);
+    assert!(drawn(&normals) > 0);
+}
@@ -0,0 +1,39 @@
+// Checks the binner's Hi-Z rejection kicks in for scenes with heavy overdraw
+
+use game::{camera::Camera, generated::textures, render_frame, scenes::VsScene};
+use glam::Vec3A;
+use renderer::graphics::{Gpu, Rgb8, Uniforms};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 180;
+
+#[test]
+fn vs_scene_rejects_hidden_tiles() {
+    let mut gpu = Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), WIDTH as f32 / HEIGHT as f32);
+
+    render_frame(&mut gpu, &camera, &VsScene::new());
+
+    // Tiles are only rasterized once the frame is generated
+    assert_eq!(gpu.depth_reject_stats().tested, 0);
+    gpu.generate_frame_buffer();
+
+    // Every tile-triangle pair is tested, the characters hide some of the background behind them
+    let stats = gpu.depth_reject_stats();
+    let geometry = gpu.stats().geometry;
+    assert_eq!(
+        stats.tested,
+        geometry.trivial_tiles + geometry.partial_tiles
+    );
+    assert!(stats.rejected > 0);
+    assert!(stats.rejected < stats.tested);
+
+    // Counters start over with each frame
+    gpu.reset_frame();
+    assert_eq!(gpu.depth_reject_stats().tested, 0);
+}
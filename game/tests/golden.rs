@@ -0,0 +1,125 @@
+// Golden image tests, each built-in scene is rendered headlessly and compared
+// against the reference PNGs in `tests/golden`.
+//
+// Run with `UPDATE_GOLDEN=1` to (re)write the references after an intended change.
+// Failures write the actual frame and a diff image into the cargo target tmp dir.
+
+use std::path::PathBuf;
+
+use game::{camera::Camera, generated::textures, render_frame, scenes::*};
+use glam::Vec3A;
+use image::{Rgb, RgbImage};
+use renderer::graphics::{Gpu, Rgb8, Uniforms};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 180;
+
+// Number of updates to run before drawing, keeps animated scenes at a fixed time
+const FRAMES: usize = 30;
+
+// Maximum difference allowed in any color channel before a pixel is considered wrong
+const CHANNEL_TOLERANCE: u8 = 8;
+
+// Number of wrong pixels allowed, to absorb float differences across platforms
+const MAX_MISMATCHED_PIXELS: usize = 16;
+
+macro_rules! golden_tests {
+    ($($name:ident: $scene:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_golden(stringify!($name), $scene);
+            }
+        )*
+    };
+}
+
+golden_tests! {
+    vs_scene: VsScene::new(),
+    multimesh_scene: MultimeshScene::new(),
+    mech_scene: MechScene::new(),
+    helmet_model_scene: HelmetModelScene::new(),
+    cube_model_scene: CubeModelScene::new(),
+    fox_model_scene: FoxModelScene::new(),
+    cube_scene: CubeScene::new(),
+    cubes_scene: CubesScene::new(),
+    plane_scene: PlaneScene::new(),
+    triangle_scene: TriangleScene::new(),
+}
+
+fn render_scene(mut scene: impl Scene<Rgb8>) -> RgbImage {
+    let mut gpu = Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), WIDTH as f32 / HEIGHT as f32);
+
+    (0..FRAMES).for_each(|_| scene.update());
+    render_frame(&mut gpu, &camera, &scene);
+
+    let pixels = gpu.generate_frame_buffer().iter().flatten().copied().collect();
+    RgbImage::from_raw(WIDTH as u32, HEIGHT as u32, pixels).unwrap()
+}
+
+fn check_golden(name: &str, scene: impl Scene<Rgb8>) {
+    let actual = render_scene(scene);
+    let reference_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        actual.save(&reference_path).unwrap();
+        return;
+    }
+
+    let reference = image::open(&reference_path)
+        .unwrap_or_else(|e| {
+            panic!("Failed to open {reference_path:?}: {e}, run with UPDATE_GOLDEN=1 to create it")
+        })
+        .to_rgb8();
+
+    assert_eq!(
+        reference.dimensions(),
+        actual.dimensions(),
+        "Reference image {name} has the wrong dimensions"
+    );
+
+    // Wrong pixels are drawn in red over a faded copy of the actual frame
+    let mut diff = RgbImage::new(WIDTH as u32, HEIGHT as u32);
+    let mut mismatched = 0;
+
+    for ((expected, actual), diff) in reference
+        .pixels()
+        .zip(actual.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let within_tolerance = expected
+            .0
+            .iter()
+            .zip(actual.0.iter())
+            .all(|(e, a)| e.abs_diff(*a) <= CHANNEL_TOLERANCE);
+
+        *diff = if within_tolerance {
+            Rgb(actual.0.map(|channel| channel / 4))
+        } else {
+            mismatched += 1;
+            Rgb([u8::MAX, 0, 0])
+        };
+    }
+
+    if mismatched > MAX_MISMATCHED_PIXELS {
+        let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+        std::fs::create_dir_all(&out_dir).unwrap();
+
+        let actual_path = out_dir.join(format!("{name}_actual.png"));
+        let diff_path = out_dir.join(format!("{name}_diff.png"));
+        actual.save(&actual_path).unwrap();
+        diff.save(&diff_path).unwrap();
+
+        panic!(
+            "{name}: {mismatched} pixels differ from the reference, see {actual_path:?} and {diff_path:?}"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+// Lines, points and wireframes go through the same pipeline as triangles,
+// so they are rendered headlessly here and checked pixel by pixel
+
+use game::{camera::Camera, debug, generated::meshes, generated::textures};
+use glam::{Mat4, Vec3, Vec3A};
+use renderer::{
+    animation::Animator,
+    graphics::{Gpu, PolygonMode, PrimitiveVertex, RenderState, Rgb8, Uniforms},
+    shaders::{Animated, BaseVertexShader, ColorBlend, TexturedLit, TexturedNormalMapLit},
+};
+use shared::{
+    mesh::{AlphaMode, Mesh},
+    vertex_parameters::VertexParametersList,
+    IndexList, TriangleIndices, VertexList,
+};
+
+const WIDTH: usize = 128;
+const HEIGHT: usize = 72;
+
+const RED: [f32; 3] = [1.0, 0.0, 0.0];
+const GREEN: [f32; 3] = [0.0, 1.0, 0.0];
+
+fn gpu() -> Gpu<Rgb8> {
+    let mut gpu = Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), WIDTH as f32 / HEIGHT as f32);
+    gpu.uniforms.view = camera.view;
+    gpu.uniforms.projection = camera.projection;
+    gpu.uniforms.camera_position = camera.position;
+    gpu
+}
+
+fn line(from: Vec3A, to: Vec3A, color: [f32; 3]) -> [PrimitiveVertex<3>; 2] {
+    [
+        PrimitiveVertex::new(from, color),
+        PrimitiveVertex::new(to, color),
+    ]
+}
+
+// Colors are perspective corrected, so can be off by a little
+fn is_color(frame: &[[u8; 3]], x: usize, y: usize, color: [f32; 3]) -> bool {
+    let pixel = frame[y * WIDTH + x];
+    pixel
+        .iter()
+        .zip(color)
+        .all(|(pixel, color)| pixel.abs_diff((color * 255.0) as u8) <= 2)
+}
+
+fn drawn_pixels(frame: &[[u8; 3]]) -> usize {
+    frame.iter().filter(|pixel| **pixel != [0; 3]).count()
+}
+
+#[test]
+fn lines_are_as_wide_as_the_render_state() {
+    let mut gpu = gpu();
+    gpu.render_state.line_width = 3.0;
+    gpu.render_lines(
+        &[line(
+            Vec3A::new(-1.0, 0.0, 0.0),
+            Vec3A::new(1.0, 0.0, 0.0),
+            RED,
+        )],
+        BaseVertexShader,
+        ColorBlend,
+    );
+
+    // The line runs along the boundary between the middle two rows
+    let frame = gpu.generate_frame_buffer();
+    let column = (0..HEIGHT)
+        .filter(|&y| is_color(frame, WIDTH / 2, y, RED))
+        .collect::<Vec<_>>();
+    assert_eq!(column, [HEIGHT / 2 - 2, HEIGHT / 2 - 1, HEIGHT / 2]);
+}
+
+#[test]
+fn lines_and_points_are_depth_tested() {
+    let mut gpu = gpu();
+    gpu.render_state.line_width = 3.0;
+    gpu.render_state.point_size = 5.0;
+
+    // A red line in front of a green one, and a green point behind the red line
+    let near = line(Vec3A::new(-1.0, 0.0, 1.0), Vec3A::new(1.0, 0.0, 1.0), RED);
+    let far = line(
+        Vec3A::new(0.0, -1.0, -1.0),
+        Vec3A::new(0.0, 1.0, -1.0),
+        GREEN,
+    );
+    gpu.render_lines(&[near, far], BaseVertexShader, ColorBlend);
+    gpu.render_points(
+        &[PrimitiveVertex::new(Vec3A::new(0.5, 0.0, -2.0), GREEN)],
+        BaseVertexShader,
+        ColorBlend,
+    );
+
+    let frame = gpu.generate_frame_buffer().to_vec();
+    assert!(is_color(&frame, WIDTH / 2, HEIGHT / 2, RED));
+    assert!(is_color(&frame, WIDTH / 2, HEIGHT / 2 - 4, GREEN));
+
+    // The point pokes out above the line
+    assert!(is_color(&frame, WIDTH / 2 + 3, HEIGHT / 2, RED));
+    assert!(is_color(&frame, WIDTH / 2 + 3, HEIGHT / 2 - 3, GREEN));
+
+    // Overlays draw over everything
+    gpu.reset_frame();
+    gpu.render_state = RenderState {
+        line_width: 3.0,
+        ..RenderState::overlay()
+    };
+    gpu.render_lines(&[near, far], BaseVertexShader, ColorBlend);
+    let frame = gpu.generate_frame_buffer();
+    assert!(is_color(frame, WIDTH / 2, HEIGHT / 2, GREEN));
+}
+
+#[test]
+fn lines_are_clipped_at_the_near_plane() {
+    let mut gpu = gpu();
+    gpu.render_state.line_width = 2.0;
+
+    // Starts behind the camera, and ends in the middle of the view
+    gpu.render_lines(
+        &[line(
+            Vec3A::new(0.0, -1.0, 10.0),
+            Vec3A::new(0.0, 0.0, 0.0),
+            RED,
+        )],
+        BaseVertexShader,
+        ColorBlend,
+    );
+
+    let frame = gpu.generate_frame_buffer();
+    assert!(is_color(frame, WIDTH / 2, HEIGHT - 1, RED));
+    assert!(is_color(frame, WIDTH / 2, HEIGHT / 4, [0.0; 3]));
+}
+
+#[test]
+fn wireframes_only_draw_the_edges() {
+    let mesh = meshes::CUBE.as_mesh();
+
+    let mut gpu = gpu();
+    gpu.render_mesh(mesh, BaseVertexShader, TexturedNormalMapLit);
+    let filled = drawn_pixels(gpu.generate_frame_buffer());
+
+    gpu.reset_frame();
+    gpu.render_state = RenderState::wireframe();
+    gpu.render_mesh(mesh, BaseVertexShader, TexturedNormalMapLit);
+    let wireframe = drawn_pixels(gpu.generate_frame_buffer());
+
+    assert!(wireframe > 0);
+    assert!(wireframe < filled / 2, "{wireframe} of {filled}");
+}
+
+#[test]
+fn wireframes_and_points_draw_back_faces() {
+    // Wound clockwise in clip space, so it faces away
+    let vertices = [(-0.5, -0.5), (0.0, 0.5), (0.5, -0.5)].map(|(x, y)| Vec3A::new(x, y, 0.5));
+    let triangle = rkyv::to_bytes::<_, 256>(&Mesh::<3> {
+        vertices: VertexList(Box::new(vertices)),
+        indices: IndexList(Box::new([TriangleIndices(0, 1, 2)])),
+        parameters: VertexParametersList::from_flat_slice(&[RED, RED, RED].concat()),
+        alpha_mode: AlphaMode::Opaque,
+    })
+    .unwrap();
+    let mesh = unsafe { rkyv::archived_root::<Mesh<3>>(&triangle) };
+
+    let mut gpu = gpu();
+    gpu.uniforms.view = Mat4::IDENTITY;
+    gpu.uniforms.projection = Mat4::IDENTITY;
+    gpu.render_mesh(mesh, BaseVertexShader, ColorBlend);
+    assert_eq!(drawn_pixels(gpu.generate_frame_buffer()), 0);
+
+    for polygon_mode in [PolygonMode::Line, PolygonMode::Point] {
+        gpu.reset_frame();
+        gpu.render_state = RenderState {
+            polygon_mode,
+            point_size: 3.0,
+            ..RenderState::default()
+        };
+        gpu.render_mesh(mesh, BaseVertexShader, ColorBlend);
+        assert!(drawn_pixels(gpu.generate_frame_buffer()) > 0);
+    }
+}
+
+#[test]
+fn skeletons_are_drawn_over_their_meshes() {
+    let mut animator = Animator::new(
+        meshes::FOX_SKL.as_skeleton(),
+        meshes::FOX_SKN.as_skin(),
+        meshes::FOX_SURVEY_ANM.as_anim(),
+    );
+    animator.update_time(0.5);
+
+    let mut gpu = gpu();
+    gpu.uniforms.model =
+        Mat4::from_translation(Vec3::new(0.0, -1.0, 0.0)) * Mat4::from_scale(Vec3::splat(0.03));
+    gpu.render_mesh(meshes::FOX.as_mesh(), Animated { animator }, TexturedLit);
+    debug::render_animator(&mut gpu, &animator);
+
+    // Bones in green, the root in red
+    let frame = gpu.generate_frame_buffer();
+    let count = |color| {
+        (0..WIDTH * HEIGHT)
+            .filter(|i| is_color(frame, i % WIDTH, i / WIDTH, color))
+            .count()
+    };
+    assert!(count(GREEN) > 0);
+    assert!(count(RED) > 0);
+}
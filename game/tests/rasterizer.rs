@@ -0,0 +1,70 @@
+// The scanline rasterizer is only a different way to find the same pixels
+
+use game::{
+    camera::Camera,
+    generated::textures,
+    render_frame,
+    scenes::{CubesScene, FoxModelScene, HelmetModelScene, Scene, VsScene},
+};
+use glam::Vec3A;
+use renderer::graphics::{Gpu, Msaa, Rasterizer, Rgb8, Uniforms};
+
+fn render(rasterizer: Rasterizer, msaa: Msaa, scene: &dyn Scene<Rgb8>) -> (Vec<[u8; 3]>, usize) {
+    let (width, height) = (250, 141);
+    let mut gpu = Gpu::new(
+        width,
+        height,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+    gpu.set_msaa(msaa);
+    gpu.render_state.rasterizer = rasterizer;
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), width as f32 / height as f32);
+
+    render_frame(&mut gpu, &camera, scene);
+    let frame = gpu.generate_frame_buffer().to_vec();
+    (frame, gpu.stats().raster.quads_tested)
+}
+
+// Spans step their depths and parameters rather than interpolating them at each stamp,
+// so colors can round to a neighbouring value
+fn same_frame(scanned: &[[u8; 3]], stamped: &[[u8; 3]]) -> bool {
+    scanned
+        .iter()
+        .flatten()
+        .zip(stamped.iter().flatten())
+        .all(|(a, b)| a.abs_diff(*b) <= 1)
+}
+
+#[test]
+fn scanlines_render_the_same_frame() {
+    let scenes: [Box<dyn Scene<Rgb8>>; 4] = [
+        Box::new(FoxModelScene::new()),
+        Box::new(HelmetModelScene::new()),
+        Box::new(VsScene::new()),
+        Box::new(CubesScene::new()),
+    ];
+
+    for (index, scene) in scenes.iter().enumerate() {
+        let (stamped, stamps_tested) = render(Rasterizer::Stamps, Msaa::Off, scene.as_ref());
+        let (scanned, spans_tested) = render(Rasterizer::Scanline, Msaa::Off, scene.as_ref());
+
+        assert!(same_frame(&scanned, &stamped), "scene {index}");
+        // Stamps outside of the spans are never visited
+        assert!(spans_tested < stamps_tested, "scene {index}");
+    }
+}
+
+// Each sample finds its own span, covering the same samples as the stamps
+#[test]
+fn multisampled_scanlines_render_the_same_frame() {
+    let scene = HelmetModelScene::new();
+
+    for msaa in [Msaa::X2, Msaa::X4] {
+        let (stamped, stamps_tested) = render(Rasterizer::Stamps, msaa, &scene);
+        let (scanned, spans_tested) = render(Rasterizer::Scanline, msaa, &scene);
+
+        assert!(same_frame(&scanned, &stamped), "{msaa:?}");
+        assert!(spans_tested < stamps_tested, "{msaa:?}");
+    }
+}
@@ -0,0 +1,104 @@
+// Resizing and render scales, the frame always fills the screen
+
+use game::{
+    camera::Camera,
+    generated::textures,
+    render_frame,
+    scenes::{HelmetModelScene, Scene},
+};
+use glam::Vec3A;
+use renderer::graphics::{Gpu, Rgb8, Uniforms, Upscale};
+
+fn gpu(width: usize, height: usize) -> Gpu<Rgb8> {
+    Gpu::new(
+        width,
+        height,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    )
+}
+
+fn render(gpu: &mut Gpu<Rgb8>, scene: &dyn Scene<Rgb8>) -> Vec<[u8; 3]> {
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), gpu.aspect_ratio());
+
+    render_frame(gpu, &camera, scene);
+    gpu.generate_frame_buffer().to_vec()
+}
+
+#[test]
+fn resizing_matches_a_new_gpu() {
+    let scene = HelmetModelScene::new();
+    let mut resized = gpu(320, 180).with_tile_size(16, 9);
+    render(&mut resized, &scene);
+
+    resized.resize(250, 141);
+    assert_eq!(resized.screen_size(), (250, 141));
+    assert_eq!(resized.tile_size(), (16, 9));
+
+    let expected = render(&mut gpu(250, 141).with_tile_size(16, 9), &scene);
+    assert!(render(&mut resized, &scene) == expected);
+}
+
+#[test]
+fn nearest_upscale_repeats_the_rendered_pixels() {
+    let scene = HelmetModelScene::new();
+    let small = render(&mut gpu(160, 90), &scene);
+
+    let mut scaled = gpu(320, 180);
+    scaled.set_render_scale(0.5);
+    assert_eq!(scaled.render_size(), (160, 90));
+    let frame = render(&mut scaled, &scene);
+
+    assert_eq!(frame.len(), 320 * 180);
+    for (index, pixel) in frame.iter().enumerate() {
+        let (x, y) = (index % 320, index / 320);
+        assert_eq!(*pixel, small[(y / 2) * 160 + x / 2], "{x}, {y}");
+    }
+}
+
+#[test]
+fn bilinear_upscale_blends_the_rendered_pixels() {
+    let scene = HelmetModelScene::new();
+    let mut scaled = gpu(320, 180);
+    scaled.set_render_scale(0.75);
+    let nearest = render(&mut scaled, &scene);
+
+    scaled.upscale = Upscale::Bilinear;
+    let bilinear = render(&mut scaled, &scene);
+
+    // Smoother, but the same picture
+    assert!(bilinear != nearest);
+    let difference = |a: &[[u8; 3]], b: &[[u8; 3]]| {
+        a.iter()
+            .zip(b)
+            .flat_map(|(a, b)| a.iter().zip(b).map(|(a, b)| a.abs_diff(*b) as f32))
+            .sum::<f32>()
+            / (a.len() * 3) as f32
+    };
+    assert!(difference(&bilinear, &nearest) < 8.0);
+}
+
+// Larger render sizes limit the guard band by the rasterizer's precision,
+// the one set comes back once the size allows it again
+#[test]
+fn guard_bands_are_restored_at_smaller_sizes() {
+    let mut gpu = gpu(1280, 720);
+    assert_eq!(gpu.guard_band(), 2.0);
+
+    gpu.resize(1920, 1080);
+    assert!(gpu.guard_band() < 2.0);
+    gpu.set_render_scale(0.5);
+    assert_eq!(gpu.guard_band(), 2.0);
+
+    gpu.set_guard_band(1.2);
+    gpu.set_render_scale(1.0);
+    assert_eq!(gpu.guard_band(), 1.2);
+    gpu.resize(320, 180);
+    assert_eq!(gpu.guard_band(), 1.2);
+}
+
+#[test]
+#[should_panic(expected = "Invalid render scale")]
+fn render_scales_stay_above_half() {
+    gpu(320, 180).set_render_scale(0.25);
+}
@@ -0,0 +1,87 @@
+// Follows the triangles of a scene through each stage of the frame
+
+use game::{camera::Camera, generated::textures, render_frame, scenes::CubesScene};
+use glam::Vec3A;
+use renderer::graphics::{Gpu, RenderStats, Rgb8, Uniforms};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 180;
+
+// The stats once the scene is recorded, and once its frame is generated
+fn render(camera_position: Vec3A) -> (RenderStats, RenderStats) {
+    let mut gpu = Gpu::new(
+        WIDTH,
+        HEIGHT,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    );
+    let camera = Camera::new(camera_position, WIDTH as f32 / HEIGHT as f32);
+
+    render_frame(&mut gpu, &camera, &CubesScene::new());
+    let recorded = gpu.stats();
+    gpu.generate_frame_buffer();
+    let generated = gpu.stats();
+
+    // Counters start over with each frame
+    gpu.reset_frame();
+    assert_eq!(gpu.stats().geometry.submitted, 0);
+
+    (recorded, generated)
+}
+
+#[test]
+fn counts_follow_triangles_through_the_stages() {
+    let (recorded, generated) = render(Vec3A::new(0.0, 0.0, 5.0));
+    let geometry = generated.geometry;
+
+    // Three cubes, all within the view, about half of them facing away
+    assert_eq!(geometry.submitted, 3 * 12);
+    assert!(geometry.backface_culled > 0);
+
+    // Nothing crosses the guard band or near plane, so nothing is clipped
+    assert_eq!(geometry.clipped_away, 0);
+    assert_eq!(
+        geometry.clipped_to_one + geometry.clipped_to_two + geometry.clipped_to_more,
+        0
+    );
+    assert_eq!(
+        geometry.submitted,
+        geometry.backface_culled + geometry.binned
+    );
+
+    // Tiles are only rasterized once the frame is generated
+    assert_eq!(recorded.raster.quads_tested, 0);
+    let raster = generated.raster;
+
+    // Every tile-triangle pair is tested against the tile's depths
+    assert_eq!(
+        raster.hi_z.tested,
+        geometry.trivial_tiles + geometry.partial_tiles
+    );
+    assert!(raster.shaded > 0);
+    assert!(raster.shaded + raster.depth_rejected <= raster.quads_tested * 4);
+
+    // The cubes are convex and don't overlap from here, so no pixel is hidden by another
+    assert_eq!(raster.depth_rejected, 0);
+}
+
+#[test]
+fn triangles_crossing_the_near_plane_are_clipped() {
+    // Close enough to the middle cube for its front to cross the near plane
+    let (_, generated) = render(Vec3A::new(0.0, 0.0, 1.5));
+    let geometry = generated.geometry;
+
+    let clipped = geometry.clipped_to_one + geometry.clipped_to_two + geometry.clipped_to_more;
+    assert!(clipped > 0);
+    // Others are entirely behind the near plane
+    assert!(geometry.clipped_away > 0);
+
+    // Clipping splits some triangles in two, so more are binned than survived culling
+    let survived = geometry.submitted - geometry.backface_culled - geometry.clipped_away;
+    assert!(geometry.binned > survived);
+
+    // The middle cube covers whole tiles, hiding the other cubes' triangles behind it
+    let raster = generated.raster;
+    assert!(geometry.trivial_tiles > 0);
+    assert!(raster.hi_z.rejected > 0);
+}
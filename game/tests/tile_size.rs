@@ -0,0 +1,64 @@
+// Tiles only change how the frame is split up for rasterizing, never what's in it
+
+use game::{
+    camera::Camera,
+    generated::textures,
+    render_frame,
+    scenes::{HelmetModelScene, Scene, VsScene},
+};
+use glam::Vec3A;
+use renderer::graphics::{Gpu, Rgb8, Uniforms};
+
+fn render(
+    (width, height): (usize, usize),
+    (tile_width, tile_height): (usize, usize),
+    scene: &dyn Scene<Rgb8>,
+) -> Vec<[u8; 3]> {
+    let mut gpu = Gpu::new(
+        width,
+        height,
+        Rgb8,
+        Uniforms::new(textures::BRICKWALL_TEX.as_texture()),
+    )
+    .with_tile_size(tile_width, tile_height);
+    let camera = Camera::new(Vec3A::new(0.0, 0.0, 5.0), width as f32 / height as f32);
+
+    render_frame(&mut gpu, &camera, scene);
+    gpu.generate_frame_buffer().to_vec()
+}
+
+#[test]
+fn tile_sizes_render_the_same_frame() {
+    let scene = HelmetModelScene::new();
+    let expected = render((320, 180), (32, 18), &scene);
+
+    // Including sizes which don't divide the screen
+    for tile_size in [(4, 1), (16, 9), (48, 20), (64, 64)] {
+        let frame = render((320, 180), tile_size, &scene);
+        assert!(frame == expected, "{tile_size:?}");
+    }
+}
+
+#[test]
+fn odd_resolutions_fill_the_whole_frame() {
+    // Neither a multiple of the tile size, nor of the rasterizer's 4 pixel stamps
+    let (width, height) = (250, 141);
+    let scene = VsScene::new();
+    let frame = render((width, height), (32, 18), &scene);
+
+    // The background covers the screen, right up to the last row and column
+    let last_row = &frame[(height - 1) * width..];
+    let last_column = frame.iter().skip(width - 1).step_by(width);
+    assert!(last_row
+        .iter()
+        .chain(last_column)
+        .all(|pixel| *pixel != [0; 3]));
+
+    assert!(render((width, height), (8, 3), &scene) == frame);
+}
+
+#[test]
+#[should_panic(expected = "Invalid tile size")]
+fn tile_widths_are_whole_stamps() {
+    render((320, 180), (30, 18), &VsScene::new());
+}
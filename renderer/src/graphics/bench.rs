@@ -0,0 +1,65 @@
+// Entry points into the pipeline's internal stages, for the micro-benchmarks in `benches/`.
+// Only built with the `bench` feature, they aren't part of the renderer's API
+
+use glam::{IVec2, Vec2, Vec4};
+use shared::vertex_parameters::VertexParameters;
+
+use super::{
+    rasterizer::{
+        EdgeStepperCombined, RenderTriangle, X_STAMP_OFFSETS, X_STEP_SIZE, Y_STAMP_OFFSETS,
+        Y_STEP_SIZE,
+    },
+    render_tile::{DEFAULT_TILE_HEIGHT, DEFAULT_TILE_WIDTH},
+    DepthFormat, Gpu, RenderTarget, Triangle,
+};
+
+/// A triangle in screen space, after triangle setup
+pub struct SetupTriangle(RenderTriangle<0>);
+
+impl SetupTriangle {
+    // Positions are in pixels, with 1/w in w
+    pub fn new(positions: [Vec4; 3]) -> Self {
+        Self(RenderTriangle::setup(Triangle {
+            positions,
+            parameters: [VertexParameters([]); 3],
+        }))
+    }
+}
+
+/// Steps the edge functions across every stamp of a default sized tile at `top_left`, as the rasterizer
+/// does, returning how many pixel centers were inside the triangle
+pub fn step_edges(triangle: &SetupTriangle, top_left: Vec2) -> u32 {
+    let mut stepper = EdgeStepperCombined::new(
+        &triangle.0,
+        top_left,
+        &X_STAMP_OFFSETS,
+        &Y_STAMP_OFFSETS,
+        X_STEP_SIZE as i32,
+        Y_STEP_SIZE as i32,
+        IVec2::ZERO,
+    );
+
+    let mut inside = 0;
+    for _ in (0..DEFAULT_TILE_HEIGHT).step_by(Y_STEP_SIZE) {
+        stepper.reset_row();
+        for _ in (0..DEFAULT_TILE_WIDTH).step_by(X_STEP_SIZE) {
+            inside += stepper
+                .points_inside_triangle_mask()
+                .move_mask()
+                .count_ones();
+            stepper.step_x();
+        }
+        stepper.step_y();
+    }
+    inside
+}
+
+/// Clips a triangle in clip space against the view and guard band,
+/// returning how many triangles are left
+pub fn clip<T: RenderTarget, D: DepthFormat>(gpu: &mut Gpu<T, D>, positions: [Vec4; 3]) -> usize {
+    let triangle = Triangle::<0> {
+        positions,
+        parameters: [VertexParameters([]); 3],
+    };
+    gpu.clip_stage(triangle).triangles().count()
+}
@@ -0,0 +1,113 @@
+use std::ops::AddAssign;
+
+use glam::{IVec2, Vec2};
+
+use super::{
+    command_buffer::BinnedTriangle,
+    rasterizer::{EdgeStepperCombined, RenderTriangle},
+    DepthFormat, Gpu, RenderTarget,
+};
+
+/// Counts of how many tile-triangle pairs reached the Hi-Z test in the raster stage,
+/// and how many of those were rejected as being entirely behind the tile's contents.
+/// Reset at the start of each frame.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct DepthRejectStats {
+    pub tested: usize,
+    pub rejected: usize,
+}
+
+impl AddAssign for DepthRejectStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.tested += rhs.tested;
+        self.rejected += rhs.rejected;
+    }
+}
+
+impl<T: RenderTarget, D: DepthFormat> Gpu<T, D> {
+    // Appends the triangle to the bins of every tile it overlaps, and to the
+    // draw's triangle list. Rasterization is deferred until the raster stage
+    pub(super) fn bin_triangle<const P: usize>(
+        &mut self,
+        triangle: RenderTriangle<P>,
+        triangles: &mut Vec<RenderTriangle<P>>,
+    ) {
+        let width = self.render_tiles.w();
+        let height = self.render_tiles.h();
+        let horizontal_count = self.render_tiles.tile_count_horizontal;
+        let vertical_count = self.render_tiles.tile_count_vertical;
+
+        let min_tile_x = (triangle.min_x / width as f32).floor() as usize;
+        let max_tile_x = (triangle.max_x / width as f32).ceil() as usize;
+
+        let min_tile_y = (triangle.min_y / height as f32).floor() as usize;
+        let max_tile_y = (triangle.max_y / height as f32).ceil() as usize;
+
+        let min_tile_x = min_tile_x.clamp(0, horizontal_count);
+        let max_tile_x = max_tile_x.clamp(0, horizontal_count);
+
+        let min_tile_y = min_tile_y.clamp(0, vertical_count);
+        let max_tile_y = max_tile_y.clamp(0, vertical_count);
+
+        // Top Left, Top Right, Bottom Left, Bottom Right
+        let x_stamp = [0, width as i32 - 1, 0, width as i32 - 1];
+        let y_stamp = [0, 0, height as i32 - 1, height as i32 - 1];
+
+        let top_left = Vec2::new((min_tile_x * width) as f32, (min_tile_y * height) as f32);
+
+        let mut stepper = EdgeStepperCombined::new(
+            &triangle,
+            top_left,
+            &x_stamp,
+            &y_stamp,
+            width as i32,
+            height as i32,
+            IVec2::ZERO,
+        );
+
+        if stepper.is_degenerate() {
+            return;
+        }
+
+        let draw = self.draw_commands.len() as u32;
+        let index = triangles.len() as u32;
+
+        for y in min_tile_y..max_tile_y {
+            stepper.reset_row();
+            for x in min_tile_x..max_tile_x {
+                let tile_index = (y * horizontal_count) + x;
+                let tile = &mut self.render_tiles[tile_index];
+
+                // We only care about triangles which overlap the tile's BB
+                let point_test_result = stepper.points_inside_triangle_mask();
+                let trivial_accept = point_test_result.all();
+
+                // First tests if any triangle point is already in the triangle,
+                // otherwise only bin those whose edges intersect the AABB
+                if trivial_accept
+                    || point_test_result.any()
+                    || tile.triangle_edges_intersect_aabb(&triangle)
+                {
+                    tile.bin.push(BinnedTriangle {
+                        draw,
+                        triangle: index,
+                        trivial_accept,
+                    });
+
+                    match trivial_accept {
+                        true => self.stats.geometry.trivial_tiles += 1,
+                        false => self.stats.geometry.partial_tiles += 1,
+                    }
+                }
+
+                // Tile shoulud just be skipped
+
+                stepper.step_x();
+            }
+            stepper.step_y();
+        }
+
+        self.stats.geometry.binned += 1;
+        triangles.push(triangle);
+    }
+}
@@ -0,0 +1,107 @@
+use shared::{mesh::ArchivedAlphaMode, types::Color};
+
+/// How fragments output by a pixel shader are combined with the frame buffer.
+/// Only opaque and alpha tested fragments write to the depth buffer, so blended
+/// draws should be submitted after everything opaque.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum BlendMode {
+    #[default]
+    Opaque,
+    // Fragments with an alpha below the cutoff are discarded
+    AlphaTest(f32),
+    AlphaBlend,
+    Additive,
+}
+
+impl BlendMode {
+    pub(super) fn writes_depth(self) -> bool {
+        matches!(self, Self::Opaque | Self::AlphaTest(_))
+    }
+
+    // Returns the color to write, or None if the fragment was discarded
+    pub(super) fn blend(self, src: Color, dst: Color) -> Option<Color> {
+        match self {
+            Self::Opaque => Some(src),
+            Self::AlphaTest(cutoff) => {
+                (src.a as f32 >= cutoff * u8::MAX as f32).then_some(Color { a: u8::MAX, ..src })
+            }
+            Self::AlphaBlend => Some(Color::rgba(
+                lerp(dst.r, src.r, src.a),
+                lerp(dst.g, src.g, src.a),
+                lerp(dst.b, src.b, src.a),
+                src.a.saturating_add(multiply(dst.a, u8::MAX - src.a)),
+            )),
+            Self::Additive => Some(Color::rgba(
+                dst.r.saturating_add(multiply(src.r, src.a)),
+                dst.g.saturating_add(multiply(src.g, src.a)),
+                dst.b.saturating_add(multiply(src.b, src.a)),
+                dst.a,
+            )),
+        }
+    }
+}
+
+// Meshes are drawn with their material's alpha mode, unless the draw sets another blend mode
+impl From<ArchivedAlphaMode> for BlendMode {
+    fn from(alpha_mode: ArchivedAlphaMode) -> Self {
+        match alpha_mode {
+            ArchivedAlphaMode::Opaque => Self::Opaque,
+            ArchivedAlphaMode::Mask(cutoff) => Self::AlphaTest(cutoff),
+            ArchivedAlphaMode::Blend => Self::AlphaBlend,
+        }
+    }
+}
+
+// Multiplies two values in the 0 -> 255 range, rounding to nearest
+fn multiply(a: u8, b: u8) -> u8 {
+    ((a as u32 * b as u32 + 127) / u8::MAX as u32) as u8
+}
+
+fn lerp(from: u8, to: u8, t: u8) -> u8 {
+    let t = t as u32;
+    ((to as u32 * t + from as u32 * (u8::MAX as u32 - t) + 127) / u8::MAX as u32) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DST: Color = Color::new(200, 100, 0);
+
+    #[test]
+    fn alpha_test_discards_below_cutoff() {
+        let mode = BlendMode::AlphaTest(0.5);
+
+        assert_eq!(mode.blend(Color::rgba(10, 20, 30, 127), DST), None);
+        assert_eq!(
+            mode.blend(Color::rgba(10, 20, 30, 128), DST),
+            Some(Color::new(10, 20, 30))
+        );
+    }
+
+    #[test]
+    fn alpha_blend_mixes_with_destination() {
+        let mode = BlendMode::AlphaBlend;
+
+        assert_eq!(mode.blend(Color::rgba(0, 0, 0, 0), DST), Some(DST));
+        assert_eq!(
+            mode.blend(Color::new(10, 20, 30), DST),
+            Some(Color::new(10, 20, 30))
+        );
+        assert_eq!(
+            mode.blend(Color::rgba(0, 200, 255, 128), DST),
+            Some(Color::new(100, 150, 128))
+        );
+    }
+
+    #[test]
+    fn additive_saturates() {
+        let mode = BlendMode::Additive;
+
+        assert_eq!(
+            mode.blend(Color::rgba(100, 100, 100, 255), DST),
+            Some(Color::new(255, 200, 100))
+        );
+        assert_eq!(mode.blend(Color::rgba(100, 100, 100, 0), DST), Some(DST));
+    }
+}
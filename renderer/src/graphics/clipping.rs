@@ -0,0 +1,268 @@
+use glam::Vec4;
+use shared::vertex_parameters::VertexParameters;
+
+use super::{DepthFormat, Gpu, Line, RenderTarget, Triangle, NEAR_PLANE};
+
+// Clipping against every plane can add at most one vertex per plane
+const MAX_POLYGON_VERTICES: usize = 3 + ClippingPlane::ALL.len();
+
+#[derive(Clone, Copy)]
+pub enum ClippingPlane {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Near,
+    Far,
+}
+
+impl ClippingPlane {
+    const ALL: [Self; 6] = [
+        Self::Near,
+        Self::Far,
+        Self::Left,
+        Self::Right,
+        Self::Top,
+        Self::Bottom,
+    ];
+
+    // Signed distance from the plane, positive values are in front of it.
+    // The side planes are pushed out by the guard band, which is 1.0 for the view itself
+    fn distance(&self, vertex: &Vec4, guard_band: f32) -> f32 {
+        match self {
+            Self::Near => vertex.z - NEAR_PLANE,
+            Self::Far => vertex.w - vertex.z,
+            Self::Left => vertex.x + vertex.w * guard_band,
+            Self::Right => vertex.w * guard_band - vertex.x,
+            Self::Top => vertex.w * guard_band - vertex.y,
+            Self::Bottom => vertex.y + vertex.w * guard_band,
+        }
+    }
+}
+
+// A triangle after clipping, which may have become a convex polygon
+pub(super) struct ClipPolygon<const P: usize> {
+    positions: [Vec4; MAX_POLYGON_VERTICES],
+    parameters: [VertexParameters<P>; MAX_POLYGON_VERTICES],
+    len: usize,
+}
+
+impl<const P: usize> ClipPolygon<P> {
+    fn empty() -> Self {
+        Self {
+            positions: [Vec4::ZERO; MAX_POLYGON_VERTICES],
+            parameters: [VertexParameters([0.0; P]); MAX_POLYGON_VERTICES],
+            len: 0,
+        }
+    }
+
+    fn from_triangle(triangle: Triangle<P>) -> Self {
+        let mut out = Self::empty();
+        out.positions[..3].copy_from_slice(&triangle.positions);
+        out.parameters[..3].copy_from_slice(&triangle.parameters);
+        out.len = 3;
+        out
+    }
+
+    fn push(&mut self, position: Vec4, parameters: VertexParameters<P>) {
+        self.positions[self.len] = position;
+        self.parameters[self.len] = parameters;
+        self.len += 1;
+    }
+
+    // Sutherland–Hodgman, keeps the parts of each edge in front of the plane
+    fn clip(&self, plane: ClippingPlane, guard_band: f32) -> Self {
+        let mut out = Self::empty();
+
+        for current in 0..self.len {
+            let next = (current + 1) % self.len;
+
+            let current_distance = plane.distance(&self.positions[current], guard_band);
+            let next_distance = plane.distance(&self.positions[next], guard_band);
+
+            if current_distance >= 0.0 {
+                out.push(self.positions[current], self.parameters[current]);
+            }
+
+            // The edge crosses the plane, so add the point where they intersect
+            if (current_distance >= 0.0) != (next_distance >= 0.0) {
+                let factor = current_distance / (current_distance - next_distance);
+                out.push(
+                    self.positions[current].lerp(self.positions[next], factor),
+                    self.parameters[current].lerp(self.parameters[next], factor),
+                );
+            }
+        }
+
+        out
+    }
+
+    // Splits the polygon into a fan of triangles, keeping the winding order
+    pub(super) fn triangles(&self) -> impl Iterator<Item = Triangle<P>> + '_ {
+        (1..self.len.saturating_sub(1)).map(|i| Triangle {
+            positions: [self.positions[0], self.positions[i], self.positions[i + 1]],
+            parameters: [
+                self.parameters[0],
+                self.parameters[i],
+                self.parameters[i + 1],
+            ],
+        })
+    }
+}
+
+// Returns true if the triangle is entirely behind the plane
+fn trivial_clip_triangle<const P: usize>(
+    plane: ClippingPlane,
+    guard_band: f32,
+    triangle: &Triangle<P>,
+) -> bool {
+    triangle
+        .positions
+        .iter()
+        .all(|position| plane.distance(position, guard_band) < 0.0)
+}
+
+impl<T: RenderTarget, D: DepthFormat> Gpu<T, D> {
+    pub(super) fn clip_stage<const P: usize>(&mut self, triangle: Triangle<P>) -> ClipPolygon<P> {
+        // Discard triangles which are entirely outside of the view
+        if ClippingPlane::ALL
+            .iter()
+            .any(|plane| trivial_clip_triangle(*plane, 1.0, &triangle))
+        {
+            self.stats.geometry.clipped_away += 1;
+            return ClipPolygon::empty();
+        }
+
+        // Most triangles are entirely within the guard band, and don't need clipping
+        let needs_clipping = ClippingPlane::ALL.iter().any(|plane| {
+            triangle
+                .positions
+                .iter()
+                .any(|position| plane.distance(position, self.guard_band) < 0.0)
+        });
+
+        let mut polygon = ClipPolygon::from_triangle(triangle);
+
+        if needs_clipping {
+            for plane in ClippingPlane::ALL {
+                polygon = polygon.clip(plane, self.guard_band);
+            }
+
+            let stats = &mut self.stats.geometry;
+            match polygon.triangles().count() {
+                0 => stats.clipped_away += 1,
+                1 => stats.clipped_to_one += 1,
+                2 => stats.clipped_to_two += 1,
+                _ => stats.clipped_to_more += 1,
+            }
+        }
+
+        polygon
+    }
+
+    // Trims the line to the part in front of every plane, as with triangles the
+    // sides are pushed out to the guard band. None if it's entirely outside
+    pub(super) fn clip_line<const P: usize>(&self, line: Line<P>) -> Option<Line<P>> {
+        let [a, b] = line.positions;
+        let (mut start, mut end) = (0.0_f32, 1.0_f32);
+
+        for plane in ClippingPlane::ALL {
+            let a_distance = plane.distance(&a, self.guard_band);
+            let b_distance = plane.distance(&b, self.guard_band);
+
+            if a_distance < 0.0 && b_distance < 0.0 {
+                return None;
+            } else if a_distance < 0.0 {
+                start = start.max(a_distance / (a_distance - b_distance));
+            } else if b_distance < 0.0 {
+                end = end.min(a_distance / (a_distance - b_distance));
+            }
+        }
+
+        if start > end {
+            return None;
+        }
+
+        let [a_parameters, b_parameters] = line.parameters;
+        Some(Line {
+            positions: [a.lerp(b, start), a.lerp(b, end)],
+            parameters: [
+                a_parameters.lerp(b_parameters, start),
+                a_parameters.lerp(b_parameters, end),
+            ],
+        })
+    }
+
+    // Points are drawn whole, or not at all
+    pub(super) fn point_is_visible(&self, position: &Vec4) -> bool {
+        ClippingPlane::ALL
+            .iter()
+            .all(|plane| plane.distance(position, self.guard_band) >= 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip_all(positions: [Vec4; 3], guard_band: f32) -> ClipPolygon<1> {
+        let mut polygon = ClipPolygon::from_triangle(Triangle {
+            positions,
+            parameters: [
+                VertexParameters([0.0]),
+                VertexParameters([1.0]),
+                VertexParameters([2.0]),
+            ],
+        });
+
+        for plane in ClippingPlane::ALL {
+            polygon = polygon.clip(plane, guard_band);
+        }
+        polygon
+    }
+
+    #[test]
+    fn clips_huge_triangles_to_the_guard_band() {
+        let guard_band = 2.0;
+
+        // Covers the whole view, and extends far past every side
+        let polygon = clip_all(
+            [
+                Vec4::new(-500.0, -500.0, 2.5, 5.0),
+                Vec4::new(500.0, -500.0, 2.5, 5.0),
+                Vec4::new(0.0, 500.0, 2.5, 5.0),
+            ],
+            guard_band,
+        );
+
+        // Only the guard band's corners remain
+        assert_eq!(polygon.len, 4);
+        assert_eq!(polygon.triangles().count(), polygon.len - 2);
+
+        for position in &polygon.positions[..polygon.len] {
+            for plane in ClippingPlane::ALL {
+                assert!(plane.distance(position, guard_band) >= -1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn clips_against_the_far_plane() {
+        // Only the first vertex is nearer than the far plane
+        let polygon = clip_all(
+            [
+                Vec4::new(0.0, 0.0, 0.5, 1.0),
+                Vec4::new(1.0, 0.0, 2.0, 1.0),
+                Vec4::new(0.0, 1.0, 2.0, 1.0),
+            ],
+            1.0,
+        );
+
+        assert_eq!(polygon.len, 3);
+        for i in 1..polygon.len {
+            let (position, parameters) = (polygon.positions[i], polygon.parameters[i]);
+            assert!((position.z - position.w).abs() < 1e-6);
+            assert!(parameters.0[0] > 0.0);
+        }
+    }
+}
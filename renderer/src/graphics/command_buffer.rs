@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use glam::Vec2;
+
+use crate::shaders::PixelShader;
+
+use super::{
+    rasterizer::RenderTriangle, render_tile::RenderTile, BlendMode, DebugView, DepthCompare,
+    DepthFormat, Msaa, RasterStats, RenderState, Uniforms,
+};
+
+// An entry in a tile's bin, refering to a triangle of a recorded draw
+#[derive(Clone, Copy)]
+pub(super) struct BinnedTriangle {
+    pub(super) draw: u32,
+    pub(super) triangle: u32,
+    // All corners of the tile are within the triangle
+    pub(super) trivial_accept: bool,
+}
+
+// How many triangles were in a tile's bin when it was last rasterized, for the debug views
+#[derive(Clone, Copy, Default)]
+pub(super) struct BinCounts {
+    pub(super) triangles: usize,
+    pub(super) trivial_accepts: usize,
+}
+
+// A draw call recorded by the geometry stage, with everything
+// the raster stage needs to shade its triangles later
+pub(super) trait DrawCommand<D: DepthFormat>: Send + Sync {
+    fn rasterize(&self, tile: &mut RenderTile<D>, binned: BinnedTriangle, stats: &mut RasterStats);
+}
+
+pub(super) struct DrawCall<PS, const P: usize> {
+    pub(super) uniforms: Uniforms,
+    pub(super) blend_mode: BlendMode,
+    pub(super) render_state: RenderState,
+    pub(super) debug_view: DebugView,
+    pub(super) triangles: Vec<RenderTriangle<P>>,
+    // Pixel shaders are only called through their type
+    pub(super) ps: PhantomData<PS>,
+}
+
+impl<PS, D, const P: usize> DrawCommand<D> for DrawCall<PS, P>
+where
+    PS: PixelShader<P>,
+    D: DepthFormat,
+{
+    fn rasterize(&self, tile: &mut RenderTile<D>, binned: BinnedTriangle, stats: &mut RasterStats) {
+        let triangle = &self.triangles[binned.triangle as usize];
+
+        // Hi-Z - Reject triangles which are entirely behind everything in the tile
+        let tile_min = Vec2::new(tile.x as f32, tile.y as f32);
+        let tile_max = tile_min + Vec2::new(tile.stride as f32, tile.height as f32);
+
+        // Hidden pixels can still change the stencil, so must be rasterized
+        let state = &self.render_state;
+        let hidden_writes_stencil = state.stencil_test && state.stencil.writes_on_depth_fail();
+
+        stats.hi_z.tested += 1;
+        let compare = state.depth_compare();
+        if !hidden_writes_stencil
+            && tile.rejects_all(triangle.max_depth_in(tile_min, tile_max), compare)
+        {
+            stats.hi_z.rejected += 1;
+            return;
+        }
+
+        // Samples can fall outside of the pixel centers the binner tested
+        if binned.trivial_accept && tile.msaa() == Msaa::Off {
+            tile.trivial_rasterize_triangle(self, triangle.clone(), stats);
+
+            // Every pixel was written, unless something nearer was already there.
+            // Failing the stencil test leaves pixels unwritten
+            let nearer_or_always = matches!(
+                compare,
+                DepthCompare::Nearer | DepthCompare::NearerOrEqual | DepthCompare::Always
+            );
+            if self.blend_mode == BlendMode::Opaque
+                && state.depth_write
+                && !state.stencil_test
+                && nearer_or_always
+            {
+                tile.z_buffer.cover(triangle.min_depth);
+            }
+        } else {
+            tile.rasterize_triangle(self, triangle.clone(), stats);
+        }
+    }
+}
+
+// Consumes the tile's bin, in the order the triangles were submitted
+pub(super) fn rasterize_tile<D: DepthFormat>(
+    tile: &mut RenderTile<D>,
+    draw_commands: &[Box<dyn DrawCommand<D>>],
+    stats: &mut RasterStats,
+) {
+    let bin = std::mem::take(&mut tile.bin);
+    tile.bin_counts = BinCounts {
+        triangles: bin.len(),
+        trivial_accepts: bin.iter().filter(|binned| binned.trivial_accept).count(),
+    };
+
+    for binned in bin.iter() {
+        draw_commands[binned.draw as usize].rasterize(tile, *binned, stats);
+    }
+
+    // Keep the allocation around for the next frame
+    tile.bin = bin;
+    tile.bin.clear();
+}
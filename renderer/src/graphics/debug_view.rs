@@ -0,0 +1,149 @@
+use glam::{Vec2, Vec3A};
+use shared::types::Color;
+
+use crate::shaders::{Derivatives, PixelShader};
+
+use super::{
+    command_buffer::BinCounts, render_tile::RenderTile, DepthFormat, Gpu, RenderTarget, Uniforms,
+};
+
+// Pixels drawn this many times or more are shown at the top of the heat map
+const MAX_OVERDRAW: u8 = 8;
+
+/// Replaces the rendered frame with a view of how it was rendered, to see where the
+/// rasterizer spends its time. Set on the [`Gpu`] at any time, without changing scene code
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum DebugView {
+    #[default]
+    None,
+    // How many times each pixel passed the depth test, from blue for once up to red
+    Overdraw,
+    // Distance from the camera, from white at the nearest pixel to black at the farthest
+    Depth,
+    // How many triangles were binned to each tile, from blue for the fewest up to red
+    TileTriangles,
+    // The share of each tile's triangles which covered all of it (green) or part of it (red)
+    TileAccept,
+    // Shaders' interpolated normals and texture coordinates in place of their colors
+    Normals,
+    TexCoords,
+}
+
+impl DebugView {
+    // Runs the pixel shader, unless the view shows one of its parameters instead
+    pub(super) fn shade<PS: PixelShader<P>, const P: usize>(
+        self,
+        uniforms: &Uniforms,
+        parameters: [f32; P],
+        derivatives: &Derivatives<P>,
+    ) -> Color {
+        match self {
+            Self::Normals => PS::normal(&parameters).map_or(Color::default(), |normal| {
+                Color::from(normal.normalize_or_zero() * 0.5 + 0.5)
+            }),
+            Self::TexCoords => PS::tex_coords(&parameters).map_or(Color::default(), |uv| {
+                let uv = uv.rem_euclid(Vec2::ONE);
+                Color::from([uv.x, uv.y, 0.0])
+            }),
+            _ => PS::run(uniforms, parameters, derivatives),
+        }
+    }
+}
+
+impl<T: RenderTarget, D: DepthFormat> Gpu<T, D> {
+    // Overwrites the tiles' frame buffers with the views of the frame's buffers and bins
+    pub(super) fn apply_debug_view(&mut self) {
+        match self.debug_view {
+            DebugView::Overdraw => {
+                for tile in self.render_tiles.tiles.iter_mut() {
+                    let counts = tile.overdraw.frame_buffer.iter();
+                    for (color, count) in tile.frame_buffer.frame_buffer.iter_mut().zip(counts) {
+                        *color = match *count {
+                            0 => Color::default(),
+                            count => heat((count - 1) as f32 / (MAX_OVERDRAW - 1) as f32),
+                        };
+                    }
+                }
+            }
+            DebugView::Depth => self.show_depth(),
+            DebugView::TileTriangles => {
+                let tiles = self.render_tiles.tiles.iter();
+                let most = tiles
+                    .map(|tile| tile.bin_counts.triangles)
+                    .max()
+                    .unwrap_or(1);
+
+                self.fill_tiles(|counts| heat(counts.triangles as f32 / most as f32));
+            }
+            DebugView::TileAccept => self.fill_tiles(|counts| {
+                let trivial = counts.trivial_accepts as f32 / counts.triangles as f32;
+                Color::from([1.0 - trivial, trivial, 0.0])
+            }),
+            DebugView::None | DebugView::Normals | DebugView::TexCoords => (),
+        }
+    }
+
+    fn show_depth(&mut self) {
+        // Stretched across the range of distances in the frame, for contrast
+        let distance = |tile: &RenderTile<D>, index| {
+            (!tile.z_buffer.is_cleared(index)).then(|| tile.z_buffer.inverse_w(index).recip())
+        };
+        let (nearest, farthest) = self.render_tiles.tiles.iter().fold(
+            (f32::INFINITY, 0.0_f32),
+            |(nearest, farthest), tile| {
+                tile.shown_pixels()
+                    .filter_map(|(index, ..)| distance(tile, index))
+                    .fold((nearest, farthest), |(nearest, farthest), distance| {
+                        (nearest.min(distance), farthest.max(distance))
+                    })
+            },
+        );
+        let range = (farthest - nearest).max(f32::EPSILON);
+
+        for tile in self.render_tiles.tiles.iter_mut() {
+            for index in 0..tile.pixels() {
+                tile.frame_buffer[index] = match distance(tile, index) {
+                    Some(distance) => Color::from(Vec3A::splat(1.0 - (distance - nearest) / range)),
+                    None => Color::default(),
+                };
+            }
+        }
+    }
+
+    // Fills each tile which had triangles binned to it with a color from its
+    // counts, darkening its edges to show the grid
+    fn fill_tiles(&mut self, color: impl Fn(BinCounts) -> Color) {
+        for tile in self.render_tiles.tiles.iter_mut() {
+            let fill = match tile.bin_counts {
+                BinCounts { triangles: 0, .. } => Color::default(),
+                counts => color(counts),
+            };
+            let edge = Color::new(fill.r / 2, fill.g / 2, fill.b / 2);
+
+            let (width, height) = (tile.width, tile.height);
+            for (index, x, y) in tile.shown_pixels() {
+                let on_edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+                tile.frame_buffer[index] = if on_edge { edge } else { fill };
+            }
+        }
+    }
+}
+
+// Blue through green to red as `t` goes from 0 to 1
+fn heat(t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0) * 2.0 - 1.0;
+    Color::from([t.max(0.0), 1.0 - t.abs(), (-t).max(0.0)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heat_runs_from_blue_to_red() {
+        assert_eq!(heat(0.0), Color::new(0, 0, 255));
+        assert_eq!(heat(0.5), Color::new(0, 255, 0));
+        assert_eq!(heat(1.0), Color::new(255, 0, 0));
+        assert_eq!(heat(2.0), heat(1.0));
+    }
+}
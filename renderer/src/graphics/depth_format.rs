@@ -0,0 +1,131 @@
+use wide::f32x4;
+
+use super::NEAR_PLANE;
+
+/// How depths in the 0 -> 1 range are stored in the depth buffer.
+/// Smaller formats use less memory bandwidth, at the cost of precision
+pub trait DepthFormat: Copy + Default + PartialEq + Send + Sync + 'static {
+    fn store(depth: f32) -> Self;
+    fn load(self) -> f32;
+
+    // Rounds depths to what can be stored, so fragments are tested at the stored precision
+    fn quantize(depths: f32x4) -> f32x4;
+}
+
+impl DepthFormat for f32 {
+    fn store(depth: f32) -> Self {
+        depth
+    }
+
+    fn load(self) -> f32 {
+        self
+    }
+
+    fn quantize(depths: f32x4) -> f32x4 {
+        depths
+    }
+}
+
+// Unsigned normalized 16 bit depth
+impl DepthFormat for u16 {
+    fn store(depth: f32) -> Self {
+        to_levels(depth, u16::MAX as u32) as u16
+    }
+
+    fn load(self) -> f32 {
+        self as f32 / u16::MAX as f32
+    }
+
+    fn quantize(depths: f32x4) -> f32x4 {
+        quantize_levels(depths, u16::MAX as u32)
+    }
+}
+
+/// Unsigned normalized 24 bit depth, in the low bits of a u32
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct U24(pub u32);
+
+impl U24 {
+    pub const MAX: u32 = (1 << 24) - 1;
+}
+
+impl DepthFormat for U24 {
+    fn store(depth: f32) -> Self {
+        Self(to_levels(depth, Self::MAX))
+    }
+
+    fn load(self) -> f32 {
+        self.0 as f32 / Self::MAX as f32
+    }
+
+    fn quantize(depths: f32x4) -> f32x4 {
+        quantize_levels(depths, Self::MAX)
+    }
+}
+
+fn to_levels(depth: f32, max: u32) -> u32 {
+    (depth.clamp(0.0, 1.0) * max as f32).round() as u32
+}
+
+fn quantize_levels(depths: f32x4, max: u32) -> f32x4 {
+    let max = f32x4::splat(max as f32);
+    (depths.max(f32x4::ZERO).min(f32x4::ONE) * max).round() / max
+}
+
+/// Which depths are stored for each distance, between the near plane and `far`,
+/// which should match the far plane of the projection. `far` can be infinite
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DepthConvention {
+    // 0 at the near plane up to 1 at the far plane
+    Standard { far: f32 },
+    // 1 at the near plane down to 0 at the far plane, which evens out the precision of floats
+    Reversed { far: f32 },
+}
+
+impl Default for DepthConvention {
+    fn default() -> Self {
+        Self::Reversed { far: f32::INFINITY }
+    }
+}
+
+impl DepthConvention {
+    pub fn far(self) -> f32 {
+        match self {
+            Self::Standard { far } | Self::Reversed { far } => far,
+        }
+    }
+
+    pub fn is_reversed(self) -> bool {
+        matches!(self, Self::Reversed { .. })
+    }
+
+    // Depth is an affine function of 1/w, returned as (scale, offset).
+    // As 1/w is, it can be linearly interpolated in screen space
+    pub(super) fn mapping(self) -> (f32, f32) {
+        let far = self.far();
+        if far <= NEAR_PLANE {
+            panic!("Invalid far plane: {far}, must be beyond the near plane {NEAR_PLANE}");
+        }
+
+        // Standard depth is k * (1 - near / w), which reverses to 1 - k + k * near / w
+        let k = if far.is_infinite() {
+            1.0
+        } else {
+            far / (far - NEAR_PLANE)
+        };
+
+        match self {
+            Self::Standard { .. } => (-k * NEAR_PLANE, k),
+            Self::Reversed { .. } => (k * NEAR_PLANE, 1.0 - k),
+        }
+    }
+
+    // Depth of everything at the far plane, which buffers are cleared to
+    pub(super) fn far_depth(self) -> f32 {
+        if self.is_reversed() {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
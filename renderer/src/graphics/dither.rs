@@ -0,0 +1,132 @@
+use glam::Vec3A;
+use shared::types::Color;
+
+use super::{render_tile::RenderTile, DepthFormat, RenderTarget};
+
+/// How colors are dithered when they are quantized to the render target's format,
+/// which hides the banding of smooth gradients on targets with few bits per channel.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum Dither {
+    #[default]
+    None,
+    // Ordered dithering, with thresholds from a Bayer matrix keyed on the screen position
+    Bayer4,
+    Bayer8,
+    // Error diffusion, errors are only spread within each tile
+    FloydSteinberg,
+}
+
+// The top left quarter of the 8x8 matrix, divided by 4, is the 4x4 matrix
+const BAYER_8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+impl Dither {
+    pub(super) fn apply<T: RenderTarget, D: DepthFormat>(
+        self,
+        target: &T,
+        tile: &mut RenderTile<D>,
+    ) {
+        match self {
+            Self::None => (),
+            Self::Bayer4 => ordered::<T, D, 4>(target, tile),
+            Self::Bayer8 => ordered::<T, D, 8>(target, tile),
+            Self::FloydSteinberg => floyd_steinberg(target, tile),
+        }
+    }
+}
+
+// Spacing between the levels of each channel, the largest error quantizing can make
+fn level_steps<T: RenderTarget>(target: &T) -> Vec3A {
+    let [r, g, b] = target.channel_levels().map(|levels| levels as f32 - 1.0);
+    u8::MAX as f32 / Vec3A::new(r, g, b)
+}
+
+fn ordered<T: RenderTarget, D: DepthFormat, const N: usize>(target: &T, tile: &mut RenderTile<D>) {
+    let steps = level_steps(target);
+    let scale = 64 / (N * N);
+
+    for (index, color) in tile.frame_buffer.frame_buffer.iter_mut().enumerate() {
+        let x = (tile.x + index % tile.stride) % N;
+        let y = (tile.y + index / tile.stride) % N;
+
+        // Centered in the -0.5 -> 0.5 range, so colors stay the same on average
+        let threshold = (BAYER_8[y][x] as usize / scale) as f32 + 0.5;
+        let offset = steps * (threshold / (N * N) as f32 - 0.5);
+
+        *color = with_rgb(*color, to_vec(*color) + offset);
+    }
+}
+
+fn floyd_steinberg<T: RenderTarget, D: DepthFormat>(target: &T, tile: &mut RenderTile<D>) {
+    // Errors carried into the current and next rows, padded by one pixel either side
+    let mut errors = vec![Vec3A::ZERO; tile.width + 2];
+    let mut next_errors = vec![Vec3A::ZERO; tile.width + 2];
+
+    // Only the shown pixels, so no error is spread into those past the width
+    for row in tile.frame_buffer.frame_buffer.chunks_exact_mut(tile.stride) {
+        for (x, color) in row[..tile.width].iter_mut().enumerate() {
+            let wanted = with_rgb(*color, to_vec(*color) + errors[x + 1]);
+            *color = target.quantize(wanted);
+
+            let error = to_vec(wanted) - to_vec(*color);
+            errors[x + 2] += error * (7.0 / 16.0);
+            next_errors[x] += error * (3.0 / 16.0);
+            next_errors[x + 1] += error * (5.0 / 16.0);
+            next_errors[x + 2] += error * (1.0 / 16.0);
+        }
+
+        std::mem::swap(&mut errors, &mut next_errors);
+        next_errors.fill(Vec3A::ZERO);
+    }
+}
+
+fn to_vec(color: Color) -> Vec3A {
+    Vec3A::new(color.r as f32, color.g as f32, color.b as f32)
+}
+
+fn with_rgb(color: Color, rgb: Vec3A) -> Color {
+    let rgb = rgb.round().clamp(Vec3A::ZERO, Vec3A::splat(u8::MAX as f32));
+    Color::rgba(rgb.x as u8, rgb.y as u8, rgb.z as u8, color.a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{DepthConvention, IndexedPalette};
+    use super::*;
+
+    // Dithering mid grey to black and white should give about as many of each
+    fn white_fraction(dither: Dither) -> f32 {
+        let target = IndexedPalette::new(&[Color::new(0, 0, 0), Color::new(255, 255, 255)]);
+        let mut tile = RenderTile::<f32>::new(0, 0, 32, 18, DepthConvention::default());
+        tile.frame_buffer
+            .frame_buffer
+            .fill(Color::new(128, 128, 128));
+
+        dither.apply(&target, &mut tile);
+
+        let pixels = &tile.frame_buffer.frame_buffer;
+        let white = pixels
+            .iter()
+            .filter(|color| target.encode(**color) == 1)
+            .count();
+        white as f32 / pixels.len() as f32
+    }
+
+    #[test]
+    fn dithering_preserves_the_average_color() {
+        assert_eq!(white_fraction(Dither::None), 1.0);
+
+        for dither in [Dither::Bayer4, Dither::Bayer8, Dither::FloydSteinberg] {
+            let fraction = white_fraction(dither);
+            assert!((fraction - 0.5).abs() < 0.05, "{dither:?}: {fraction}");
+        }
+    }
+}
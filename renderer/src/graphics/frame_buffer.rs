@@ -0,0 +1,31 @@
+use std::ops::{Index, IndexMut};
+
+pub struct FrameBuffer<T> {
+    pub frame_buffer: Box<[T]>,
+}
+
+impl<T: Copy + Default> FrameBuffer<T> {
+    pub fn new(pixels: usize) -> Self {
+        Self {
+            frame_buffer: vec![T::default(); pixels].into_boxed_slice(),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.frame_buffer.fill(T::default());
+    }
+}
+
+impl<T> Index<usize> for FrameBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.frame_buffer[index]
+    }
+}
+
+impl<T> IndexMut<usize> for FrameBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.frame_buffer[index]
+    }
+}
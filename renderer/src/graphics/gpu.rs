@@ -0,0 +1,474 @@
+use std::marker::PhantomData;
+
+use glam::{Mat3, Vec4, Vec4Swizzles};
+use shared::{mesh::ArchivedMesh, types::Color};
+
+use crate::shaders::{PixelShader, VertexShader};
+
+use super::{
+    command_buffer::{DrawCall, DrawCommand},
+    rasterizer::{RenderTriangle, MAX_RASTER_AREA, X_STEP_SIZE},
+    render_tile::{DEFAULT_TILE_HEIGHT, DEFAULT_TILE_WIDTH},
+    stats::Span,
+    tile_manager::TileManager,
+    BlendMode, DebugView, DepthConvention, DepthFormat, DepthRejectStats, Dither, Line, Msaa,
+    PolygonMode, RenderState, RenderStats, RenderTarget, Triangle, Uniforms, Upscale, ZBuffer,
+};
+
+const DEFAULT_GUARD_BAND: f32 = 2.0;
+
+/// Renders into a frame buffer for the render target `T`,
+/// with a depth buffer stored in the format `D`
+pub struct Gpu<T: RenderTarget, D: DepthFormat = f32> {
+    // The resolution rendered at, the output's scaled by the render scale
+    pub(super) screen_width: usize,
+    pub(super) screen_height: usize,
+    output_width: usize,
+    output_height: usize,
+    render_scale: f32,
+    target: T,
+    frame_buffer: Box<[T::Pixel]>,
+    pub uniforms: Uniforms,
+    pub blend_mode: BlendMode,
+    pub render_state: RenderState,
+    pub dither: Dither,
+    pub debug_view: DebugView,
+    pub upscale: Upscale,
+    // As set, kept to restore it once the render size allows again
+    requested_guard_band: f32,
+    // The requested guard band, lowered to the render size's limit
+    pub(super) guard_band: f32,
+    pub(super) stats: RenderStats,
+    pub(super) draw_commands: Vec<Box<dyn DrawCommand<D>>>,
+    pub(super) render_tiles: TileManager<D>,
+}
+
+impl<T: RenderTarget> Gpu<T> {
+    pub fn new(screen_width: usize, screen_height: usize, target: T, uniforms: Uniforms) -> Self {
+        Self::with_depth_format(screen_width, screen_height, target, uniforms)
+    }
+}
+
+impl<T: RenderTarget, D: DepthFormat> Gpu<T, D> {
+    // Picks the depth format from the type, such as `Gpu::<Rgb8, u16>::with_depth_format`
+    pub fn with_depth_format(
+        screen_width: usize,
+        screen_height: usize,
+        target: T,
+        uniforms: Uniforms,
+    ) -> Self {
+        let guard_band = DEFAULT_GUARD_BAND.min(max_guard_band(screen_width, screen_height));
+
+        Self {
+            screen_height,
+            screen_width,
+            output_width: screen_width,
+            output_height: screen_height,
+            render_scale: 1.0,
+            target,
+            frame_buffer: vec![T::Pixel::default(); screen_height * screen_width]
+                .into_boxed_slice(),
+            uniforms,
+            blend_mode: BlendMode::default(),
+            render_state: RenderState::default(),
+            dither: Dither::default(),
+            debug_view: DebugView::default(),
+            upscale: Upscale::default(),
+            requested_guard_band: DEFAULT_GUARD_BAND,
+            guard_band,
+            stats: RenderStats::default(),
+            draw_commands: Vec::new(),
+            render_tiles: TileManager::new(
+                screen_width,
+                screen_height,
+                DEFAULT_TILE_WIDTH,
+                DEFAULT_TILE_HEIGHT,
+                DepthConvention::default(),
+            ),
+        }
+    }
+
+    // Replaces the default 32 x 18 tiles. Smaller tiles bin triangles to more of them, larger
+    // ones are less often hidden entirely. The screen needn't be a multiple of the size, the
+    // tiles on its right and bottom edges are cut short. Widths must be a multiple of 4
+    pub fn with_tile_size(mut self, tile_width: usize, tile_height: usize) -> Self {
+        if tile_width == 0 || !tile_width.is_multiple_of(X_STEP_SIZE) || tile_height == 0 {
+            panic!("Invalid tile size: {tile_width} x {tile_height}, the width must be a multiple of {X_STEP_SIZE}");
+        }
+
+        self.rebuild_tiles(tile_width, tile_height);
+        self
+    }
+
+    pub fn tile_size(&self) -> (usize, usize) {
+        (self.render_tiles.w(), self.render_tiles.h())
+    }
+
+    // Changes the size of the frame buffer, such as when the window is resized.
+    // The camera's projection should be rebuilt for the new aspect ratio.
+    // Clears every buffer, so should be called between frames
+    pub fn resize(&mut self, screen_width: usize, screen_height: usize) {
+        if screen_width == 0 || screen_height == 0 {
+            panic!("Invalid screen size: {screen_width} x {screen_height}");
+        }
+
+        (self.output_width, self.output_height) = (screen_width, screen_height);
+        self.frame_buffer =
+            vec![T::Pixel::default(); screen_width * screen_height].into_boxed_slice();
+        self.rebuild_tiles(self.render_tiles.w(), self.render_tiles.h());
+    }
+
+    pub fn screen_size(&self) -> (usize, usize) {
+        (self.output_width, self.output_height)
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    // Renders at a fraction of the screen's size, from 0.5 to 1.0, then stretches the frame to
+    // fill it with the `upscale` filter. Lower scales shade fewer pixels, for holding the
+    // framerate in heavy scenes. Clears every buffer, so should be set between frames
+    pub fn set_render_scale(&mut self, render_scale: f32) {
+        if !(0.5..=1.0).contains(&render_scale) {
+            panic!("Invalid render scale: {render_scale}, must be between 0.5 and 1.0");
+        }
+
+        self.render_scale = render_scale;
+        self.rebuild_tiles(self.render_tiles.w(), self.render_tiles.h());
+    }
+
+    // The resolution the tiles render at, smaller than the screen below a render scale of 1.0
+    pub fn render_size(&self) -> (usize, usize) {
+        (self.screen_width, self.screen_height)
+    }
+
+    // Reallocates the tiles for the output size and render scale, keeping their settings
+    fn rebuild_tiles(&mut self, tile_width: usize, tile_height: usize) {
+        let scaled = |size: usize| ((size as f32 * self.render_scale).round() as usize).max(1);
+        self.screen_width = scaled(self.output_width);
+        self.screen_height = scaled(self.output_height);
+
+        let (convention, msaa) = (self.depth_convention(), self.msaa());
+        self.render_tiles = TileManager::new(
+            self.screen_width,
+            self.screen_height,
+            tile_width,
+            tile_height,
+            convention,
+        );
+        self.set_msaa(msaa);
+
+        self.guard_band = self
+            .requested_guard_band
+            .min(max_guard_band(self.screen_width, self.screen_height));
+        self.draw_commands.clear();
+    }
+
+    pub fn reset_frame(&mut self) {
+        self.render_tiles.reset_frame();
+        self.draw_commands.clear();
+        self.stats = RenderStats::default();
+    }
+
+    pub fn depth_convention(&self) -> DepthConvention {
+        self.render_tiles.tiles[0].z_buffer.convention()
+    }
+
+    // The far plane of the convention should match the projection's.
+    // Clears the depth buffer, so should be set between frames
+    pub fn set_depth_convention(&mut self, convention: DepthConvention) {
+        for tile in self.render_tiles.tiles.iter_mut() {
+            tile.set_depth_convention(convention);
+        }
+    }
+
+    pub fn msaa(&self) -> Msaa {
+        self.render_tiles.tiles[0].msaa()
+    }
+
+    // Each extra sample adds a depth, stencil and color buffer to every tile.
+    // Clears them, so should be set between frames
+    pub fn set_msaa(&mut self, msaa: Msaa) {
+        for tile in self.render_tiles.tiles.iter_mut() {
+            tile.set_msaa(msaa);
+        }
+    }
+
+    // Width / height, for building projections which fill the screen
+    pub fn aspect_ratio(&self) -> f32 {
+        self.output_width as f32 / self.output_height as f32
+    }
+
+    pub fn depth_reject_stats(&self) -> DepthRejectStats {
+        self.stats.raster.hi_z
+    }
+
+    // Counters of the frame so far, see `RenderStats` for when each is complete
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    pub fn guard_band(&self) -> f32 {
+        self.guard_band
+    }
+
+    // Triangles are only clipped against the sides of the guard band, a multiple of
+    // the screen's size, leaving the rasterizer to skip the pixels off screen.
+    // Larger values clip less often, but are limited by the rasterizer's precision.
+    // Resizing to a larger render size lowers it as needed, and restores it once the size allows
+    pub fn set_guard_band(&mut self, guard_band: f32) {
+        let max = max_guard_band(self.screen_width, self.screen_height);
+        if !(1.0..=max).contains(&guard_band) {
+            panic!("Invalid guard band: {guard_band}, must be between 1.0 and {max}");
+        }
+        self.requested_guard_band = guard_band;
+        self.guard_band = guard_band;
+    }
+
+    pub fn render_mesh<VS, const VSIN: usize, PS, const PSIN: usize>(
+        &mut self,
+        mesh: &ArchivedMesh<VSIN>,
+        vs: VS,
+        _ps: PS,
+    ) where
+        VS: VertexShader<VSIN, PSIN>,
+        PS: PixelShader<PSIN>,
+    {
+        let span = Span::start();
+        let vertex_list = &mesh.vertices.0;
+        let mut triangles = Vec::new();
+        let indices = &mesh.indices.0;
+
+        let inverted = self.uniforms.model.determinant().is_sign_negative();
+
+        // An opaque blend mode defers to the material's, such as glTF's alpha masks
+        let blend_mode = match self.blend_mode {
+            BlendMode::Opaque => BlendMode::from(mesh.alpha_mode),
+            blend_mode => blend_mode,
+        };
+
+        // Iterate each triangle of the mesh
+        for triangle_indices in indices.iter() {
+            let [ai, mut bi, mut ci] = [
+                triangle_indices.0 as usize,
+                triangle_indices.1 as usize,
+                triangle_indices.2 as usize,
+            ];
+
+            // Handle inverted Scale
+            if inverted {
+                std::mem::swap(&mut bi, &mut ci)
+            }
+
+            let a = vertex_list[ai];
+            let b = vertex_list[bi];
+            let c = vertex_list[ci];
+            let params = &mesh.parameters;
+
+            // Run Vertex shader on every vertexs
+            // This should output them into clip space
+            let a_clip = vs.run(ai, &self.uniforms, a, params.0[ai].0);
+            let b_clip = vs.run(bi, &self.uniforms, b, params.0[bi].0);
+            let c_clip = vs.run(ci, &self.uniforms, c, params.0[ci].0);
+
+            self.stats.geometry.submitted += 1;
+
+            // Culling Stage, wireframes and points show the back faces too
+            let fill = self.render_state.polygon_mode == PolygonMode::Fill;
+            if fill && is_backfacing(a_clip.position, b_clip.position, c_clip.position) {
+                self.stats.geometry.backface_culled += 1;
+                continue; // Skip this triangle if it's a backface
+            }
+
+            // Clipping Stage - Triangle is being re-wound here as the later
+            // Y-flip for NDC -> Screen space reverses winding order
+            let triangle = Triangle {
+                positions: [a_clip.position, c_clip.position, b_clip.position],
+                parameters: [a_clip.parameters, c_clip.parameters, b_clip.parameters],
+            };
+
+            match self.render_state.polygon_mode {
+                // Triangle Setup -> Pass to binner
+                PolygonMode::Fill => {
+                    for triangle in self.clip_stage(triangle).triangles() {
+                        let triangle = self.tri_clip_to_screen_space(triangle);
+                        let triangle = RenderTriangle::setup(triangle);
+
+                        self.bin_triangle(triangle, &mut triangles);
+                    }
+                }
+                PolygonMode::Line => {
+                    for (start, end) in [(0, 1), (1, 2), (2, 0)] {
+                        let line = Line {
+                            positions: [triangle.positions[start], triangle.positions[end]],
+                            parameters: [triangle.parameters[start], triangle.parameters[end]],
+                        };
+                        self.bin_line(line, &mut triangles);
+                    }
+                }
+                PolygonMode::Point => {
+                    for (position, parameters) in triangle.positions.iter().zip(triangle.parameters)
+                    {
+                        self.bin_point(*position, parameters, &mut triangles);
+                    }
+                }
+            }
+        }
+
+        self.record_draw::<PS, PSIN>(triangles, blend_mode);
+        self.stats.timings.geometry += span.elapsed();
+    }
+
+    // Record the draw, its triangles are shaded in the raster stage
+    pub(super) fn record_draw<PS: PixelShader<P>, const P: usize>(
+        &mut self,
+        triangles: Vec<RenderTriangle<P>>,
+        blend_mode: BlendMode,
+    ) {
+        if !triangles.is_empty() {
+            self.draw_commands.push(Box::new(DrawCall {
+                uniforms: self.uniforms.clone(),
+                blend_mode,
+                render_state: self.render_state,
+                debug_view: self.debug_view,
+                triangles,
+                ps: PhantomData::<PS>,
+            }));
+        }
+    }
+
+    // Raster stage, tiles are independent of each other so they can be processed in any order
+    pub(super) fn rasterize_tiles(&mut self) {
+        let span = Span::start();
+
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+        {
+            self.stats.raster +=
+                super::parallel::rasterize_tiles(&mut self.render_tiles.tiles, &self.draw_commands);
+        }
+
+        #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+        for tile in self.render_tiles.tiles.iter_mut() {
+            super::command_buffer::rasterize_tile(
+                tile,
+                &self.draw_commands,
+                &mut self.stats.raster,
+            );
+        }
+
+        self.draw_commands.clear();
+        self.stats.timings.raster += span.elapsed();
+    }
+
+    // Converts a triangle from clip space into screen space
+    fn tri_clip_to_screen_space<const P: usize>(
+        &self,
+        mut clip_space_triangle: Triangle<P>,
+    ) -> Triangle<P> {
+        clip_space_triangle.positions[0] = self.clip_to_screen(clip_space_triangle.positions[0]);
+        clip_space_triangle.positions[1] = self.clip_to_screen(clip_space_triangle.positions[1]);
+        clip_space_triangle.positions[2] = self.clip_to_screen(clip_space_triangle.positions[2]);
+
+        clip_space_triangle
+    }
+
+    // Rasterizes everything drawn so far, then stitches together a frame buffer
+    pub fn generate_frame_buffer(&mut self) -> &[T::Pixel] {
+        self.rasterize_tiles();
+        let span = Span::start();
+
+        for tile in self.render_tiles.tiles.iter_mut() {
+            tile.resolve();
+        }
+
+        // Debug views show exact values, so aren't dithered
+        if self.debug_view == DebugView::None {
+            for tile in self.render_tiles.tiles.iter_mut() {
+                self.dither.apply(&self.target, tile);
+            }
+        } else {
+            self.apply_debug_view();
+        }
+
+        let mut frame = std::mem::take(&mut self.frame_buffer);
+        if self.render_size() == self.screen_size() {
+            self.stitch_tiles(&mut frame, |pixel, color| {
+                *pixel = self.target.encode(color)
+            });
+        } else {
+            // Stitched at the render size first, as the filters sample across tiles
+            let mut colors = vec![Color::default(); self.screen_width * self.screen_height];
+            self.stitch_tiles(&mut colors, |pixel, color| *pixel = color);
+            let size = self.render_size();
+            self.upscale
+                .apply(&self.target, &colors, size, &mut frame, self.output_width);
+        }
+        self.frame_buffer = frame;
+
+        self.stats.timings.resolve += span.elapsed();
+        &self.frame_buffer
+    }
+
+    // Copies each row of the tiles into a render sized buffer, skipping the pixels past their widths
+    fn stitch_tiles<P>(&self, out: &mut [P], write: impl Fn(&mut P, Color)) {
+        for tile in self.render_tiles.tiles.iter() {
+            let rows = tile.frame_buffer.frame_buffer.chunks_exact(tile.stride);
+
+            for (y, source) in (tile.y..tile.y + tile.height).zip(rows) {
+                let start = y * self.screen_width + tile.x;
+                out[start..start + tile.width]
+                    .iter_mut()
+                    .zip(source)
+                    .for_each(|(pixel, color)| write(pixel, *color));
+            }
+        }
+    }
+
+    /// The depth buffer of the frame, in its format and convention, at the render size.
+    /// Only complete after [`Gpu::generate_frame_buffer`], until the next reset
+    pub fn depth_buffer(&self) -> Box<[D]> {
+        self.stitch_depths(|z_buffer, index| z_buffer.z_buffer[index])
+    }
+
+    // Gathers a value from each pixel of the tiles' depth buffers into a screen sized buffer
+    pub(super) fn stitch_depths<V: Copy + Default>(
+        &self,
+        value: impl Fn(&ZBuffer<D>, usize) -> V,
+    ) -> Box<[V]> {
+        let mut out = vec![V::default(); self.screen_width * self.screen_height];
+
+        for tile in self.render_tiles.tiles.iter() {
+            for (index, x, y) in tile.shown_pixels() {
+                out[(tile.y + y) * self.screen_width + tile.x + x] = value(&tile.z_buffer, index);
+            }
+        }
+
+        out.into_boxed_slice()
+    }
+
+    // Converts a clip space vertex into screen space, storing 1/w in w
+    pub fn clip_to_screen(&self, clip_space_vertex: Vec4) -> Vec4 {
+        // Move to cartesian coordinates
+        // Sace the recip of W for perspective correction later
+        let w_recip = clip_space_vertex.w.recip();
+        let clip_space_vertex = clip_space_vertex / clip_space_vertex.w;
+
+        // Convert NDC coordinates to screen space
+        let screen_x = (clip_space_vertex.x + 1.0) * (self.screen_width as f32 / 2.0);
+        let screen_y = (1.0 - clip_space_vertex.y) * (self.screen_height as f32 / 2.0);
+
+        Vec4::new(screen_x, screen_y, clip_space_vertex.z, w_recip)
+    }
+}
+
+// Largest guard band which keeps every triangle within the rasterizer's limits
+fn max_guard_band(screen_width: usize, screen_height: usize) -> f32 {
+    (MAX_RASTER_AREA / (screen_width * screen_height) as f32)
+        .sqrt()
+        .max(1.0)
+}
+
+fn is_backfacing(a: Vec4, b: Vec4, c: Vec4) -> bool {
+    Mat3::from_cols(a.xyw(), b.xyw(), c.xyw()).determinant() < 0.0
+}
@@ -0,0 +1,212 @@
+use glam::Vec3A;
+
+// Most lights a draw can be lit by, keeps the light list a fixed size
+pub const MAX_LIGHTS: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LightKind {
+    // Lights everything from the same direction, such as the sun
+    Directional {
+        direction: Vec3A,
+    },
+    // Fades to nothing at its range
+    Point {
+        position: Vec3A,
+        range: f32,
+    },
+    // A point light limited to a cone, fading between the inner and outer angles
+    Spot {
+        position: Vec3A,
+        direction: Vec3A,
+        range: f32,
+        inner_angle: f32,
+        outer_angle: f32,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Light {
+    pub kind: LightKind,
+    pub color: Vec3A,
+    pub intensity: f32,
+}
+
+// The light arriving at a point
+pub struct IncomingLight {
+    pub to_light: Vec3A,
+    pub radiance: Vec3A,
+}
+
+impl Light {
+    pub fn directional(direction: Vec3A) -> Self {
+        Self::white(LightKind::Directional {
+            direction: direction.normalize(),
+        })
+    }
+
+    pub fn point(position: Vec3A, range: f32) -> Self {
+        Self::white(LightKind::Point { position, range })
+    }
+
+    // Angles are in radians, from the center of the cone to its edge
+    pub fn spot(
+        position: Vec3A,
+        direction: Vec3A,
+        range: f32,
+        inner_angle: f32,
+        outer_angle: f32,
+    ) -> Self {
+        Self::white(LightKind::Spot {
+            position,
+            direction: direction.normalize(),
+            range,
+            inner_angle,
+            outer_angle,
+        })
+    }
+
+    fn white(kind: LightKind) -> Self {
+        Self {
+            kind,
+            color: Vec3A::ONE,
+            intensity: 1.0,
+        }
+    }
+
+    pub fn with_color(mut self, color: Vec3A) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn incoming(&self, position: Vec3A) -> IncomingLight {
+        let (to_light, attenuation) = match self.kind {
+            LightKind::Directional { direction } => (-direction, 1.0),
+            LightKind::Point {
+                position: light,
+                range,
+            } => {
+                let offset = light - position;
+                (
+                    offset.normalize_or_zero(),
+                    range_attenuation(offset.length(), range),
+                )
+            }
+            LightKind::Spot {
+                position: light,
+                direction,
+                range,
+                inner_angle,
+                outer_angle,
+            } => {
+                let offset = light - position;
+                let to_light = offset.normalize_or_zero();
+
+                // Smoothly fades out between the cosines of the angles
+                let (inner, outer) = (inner_angle.cos(), outer_angle.cos());
+                let cone = ((-to_light).dot(direction) - outer) / (inner - outer).max(1e-4);
+                let cone = cone.clamp(0.0, 1.0);
+
+                (
+                    to_light,
+                    range_attenuation(offset.length(), range) * cone * cone,
+                )
+            }
+        };
+
+        IncomingLight {
+            to_light,
+            radiance: self.color * (self.intensity * attenuation),
+        }
+    }
+}
+
+// Keeps the full intensity nearby, then falls smoothly to zero at the range
+fn range_attenuation(distance: f32, range: f32) -> f32 {
+    let falloff = (1.0 - (distance / range).powi(4)).max(0.0);
+    falloff * falloff
+}
+
+/// A fixed size list of lights, so uniforms can be copied into each draw without allocating
+#[derive(Clone, Copy, Debug)]
+pub struct Lights {
+    lights: [Light; MAX_LIGHTS],
+    len: usize,
+}
+
+impl Default for Lights {
+    fn default() -> Self {
+        Self {
+            lights: [Light::directional(Vec3A::NEG_Z); MAX_LIGHTS],
+            len: 0,
+        }
+    }
+}
+
+impl Lights {
+    pub fn push(&mut self, light: Light) {
+        if self.len == MAX_LIGHTS {
+            panic!("Too many lights, the max is {MAX_LIGHTS}");
+        }
+        self.lights[self.len] = light;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Light> {
+        self.lights[..self.len].iter()
+    }
+}
+
+impl<const N: usize> From<[Light; N]> for Lights {
+    fn from(lights: [Light; N]) -> Self {
+        let mut out = Self::default();
+        lights.into_iter().for_each(|light| out.push(light));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_lights_fade_out_at_their_range() {
+        let light = Light::point(Vec3A::ZERO, 10.0).with_intensity(2.0);
+
+        let near = light.incoming(Vec3A::new(0.0, 0.0, 1.0));
+        assert!(near.to_light.abs_diff_eq(Vec3A::NEG_Z, 1e-6));
+        assert!(near.radiance.x > 1.99);
+
+        assert_eq!(
+            light.incoming(Vec3A::new(0.0, 10.0, 0.0)).radiance,
+            Vec3A::ZERO
+        );
+    }
+
+    #[test]
+    fn spot_lights_only_light_their_cone() {
+        let light = Light::spot(Vec3A::ZERO, Vec3A::NEG_Y, 100.0, 0.2, 0.4);
+
+        let inside = light.incoming(Vec3A::new(0.0, -5.0, 0.0));
+        let outside = light.incoming(Vec3A::new(5.0, -5.0, 0.0));
+
+        assert!(inside.radiance.x > 0.99);
+        assert_eq!(outside.radiance, Vec3A::ZERO);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(feature = "bench")]
+pub mod bench;
+mod binner;
+mod blend_mode;
+mod clipping;
+mod command_buffer;
+mod debug_view;
+mod depth_format;
+mod dither;
+mod frame_buffer;
+mod gpu;
+mod light;
+mod msaa;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+mod parallel;
+mod primitives;
+mod rasterizer;
+mod render_state;
+mod render_target;
+mod render_tile;
+mod shadow_map;
+mod stats;
+mod stencil_buffer;
+mod tile_manager;
+mod upscale;
+mod z_buffer;
+
+pub use binner::DepthRejectStats;
+pub use blend_mode::BlendMode;
+pub use debug_view::DebugView;
+pub use depth_format::{DepthConvention, DepthFormat, U24};
+pub use dither::Dither;
+pub use frame_buffer::FrameBuffer;
+pub use gpu::Gpu;
+pub use light::{IncomingLight, Light, LightKind, Lights, MAX_LIGHTS};
+pub use msaa::Msaa;
+pub use primitives::PrimitiveVertex;
+pub use render_state::{DepthCompare, PolygonMode, Rasterizer, RenderState};
+pub use render_target::*;
+pub use shadow_map::ShadowMap;
+use shared::{texture::ArchivedTexture, vertex_parameters::VertexParameters};
+pub use stats::{GeometryStats, RasterStats, RenderStats, StageTimings};
+pub use stencil_buffer::{StencilBuffer, StencilCompare, StencilOp, StencilState};
+pub use upscale::Upscale;
+pub use z_buffer::ZBuffer;
+
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3A, Vec4};
+
+// Distance to the near clipping plane, projections must be built with this value
+pub const NEAR_PLANE: f32 = 0.1;
+
+#[derive(Clone)]
+pub(crate) struct Triangle<const P: usize> {
+    positions: [Vec4; 3],
+    parameters: [VertexParameters<P>; 3],
+}
+
+#[derive(Clone)]
+pub(crate) struct Line<const P: usize> {
+    positions: [Vec4; 2],
+    parameters: [VertexParameters<P>; 2],
+}
+
+#[derive(Clone)]
+pub struct Uniforms {
+    // Pixel Shader
+    pub lights: Lights,
+    // Only the first lights up to the limit are used, so cheap draws can skip the rest
+    pub light_limit: usize,
+    pub ambient_light: f32,
+    pub camera_position: Vec3A,
+    // Shadows the first light, shared between every draw of the frame
+    pub shadow_map: Option<Arc<ShadowMap>>,
+    pub diffuse: &'static ArchivedTexture,
+    pub normal: &'static ArchivedTexture,
+    pub emissive: &'static ArchivedTexture,
+    pub occlusion: &'static ArchivedTexture,
+    // Roughness in green, metalness in blue, as in glTF
+    pub metallic_roughness: &'static ArchivedTexture,
+
+    // Vertex Shader
+    pub model: Mat4,
+    pub view: Mat4,
+    pub projection: Mat4,
+}
+
+impl Uniforms {
+    // Binds the texture to every texture slot, scenes are expected
+    // to override the ones they sample from
+    pub fn new(texture: &'static ArchivedTexture) -> Self {
+        Self {
+            lights: Lights::from([Light::point(Vec3A::ZERO, f32::INFINITY).with_intensity(1.25)]),
+            light_limit: MAX_LIGHTS,
+            ambient_light: 0.15,
+            camera_position: Vec3A::default(),
+            shadow_map: None,
+            diffuse: texture,
+            normal: texture,
+            emissive: texture,
+            occlusion: texture,
+            metallic_roughness: texture,
+
+            model: Mat4::IDENTITY,
+            view: Mat4::IDENTITY,
+            projection: Mat4::IDENTITY,
+        }
+    }
+
+    // The lights this draw is lit by
+    pub fn lights(&self) -> impl Iterator<Item = &Light> {
+        self.lights.iter().take(self.light_limit)
+    }
+
+    // The light arriving at a position from each light, the first is shadowed by the shadow map
+    pub fn incoming_lights(&self, position: Vec3A) -> impl Iterator<Item = IncomingLight> + '_ {
+        self.lights().enumerate().map(move |(index, light)| {
+            let mut incoming = light.incoming(position);
+            if let (0, Some(shadow_map)) = (index, &self.shadow_map) {
+                incoming.radiance *= shadow_map.visibility(position);
+            }
+            incoming
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use glam::IVec2;
+use shared::types::Color;
+
+use super::{
+    render_tile::RenderTile, DepthCompare, DepthConvention, DepthFormat, FrameBuffer,
+    StencilBuffer, ZBuffer,
+};
+
+/// Multisample anti-aliasing. Coverage, depth and stencil are tested at each sample,
+/// but pixels are only shaded once, at their center. The samples' colors are averaged
+/// when the frame buffer is generated, smoothing the edges of triangles.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum Msaa {
+    #[default]
+    Off,
+    X2,
+    X4,
+}
+
+pub(super) const MAX_SAMPLES: usize = 4;
+
+// Positions relative to the pixel center, in steps of the rasterizer's sub-pixel grid
+const CENTER: [IVec2; 1] = [IVec2::ZERO];
+const X2_OFFSETS: [IVec2; 2] = [IVec2::new(-4, -4), IVec2::new(4, 4)];
+// A rotated grid, so near vertical and horizontal edges still get 4 levels of coverage
+const X4_OFFSETS: [IVec2; 4] = [
+    IVec2::new(-2, -6),
+    IVec2::new(6, -2),
+    IVec2::new(-6, 2),
+    IVec2::new(2, 6),
+];
+
+impl Msaa {
+    pub fn samples(self) -> usize {
+        self.sample_offsets().len()
+    }
+
+    pub(super) fn sample_offsets(self) -> &'static [IVec2] {
+        match self {
+            Self::Off => &CENTER,
+            Self::X2 => &X2_OFFSETS,
+            Self::X4 => &X4_OFFSETS,
+        }
+    }
+}
+
+// Buffers of a tile for each sample after the first, which uses the tile's own
+pub(super) struct SampleBuffers<D: DepthFormat> {
+    z_buffer: ZBuffer<D>,
+    stencil_buffer: StencilBuffer,
+    frame_buffer: FrameBuffer<Color>,
+}
+
+impl<D: DepthFormat> SampleBuffers<D> {
+    fn new(pixels: usize, convention: DepthConvention) -> Self {
+        Self {
+            z_buffer: ZBuffer::new(pixels, convention),
+            stencil_buffer: StencilBuffer::new(pixels),
+            frame_buffer: FrameBuffer::new(pixels),
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.z_buffer.clear();
+        self.stencil_buffer.clear();
+        self.frame_buffer.clear();
+    }
+}
+
+impl<D: DepthFormat> RenderTile<D> {
+    pub(super) fn msaa(&self) -> Msaa {
+        self.msaa
+    }
+
+    // Reallocates the sample buffers, so should be set between frames
+    pub(super) fn set_msaa(&mut self, msaa: Msaa) {
+        let (pixels, convention) = (self.pixels(), self.z_buffer.convention());
+        self.msaa = msaa;
+        self.samples = (1..msaa.samples())
+            .map(|_| SampleBuffers::new(pixels, convention))
+            .collect();
+    }
+
+    pub(super) fn set_depth_convention(&mut self, convention: DepthConvention) {
+        let pixels = self.pixels();
+        self.z_buffer = ZBuffer::new(pixels, convention);
+        for sample in self.samples.iter_mut() {
+            sample.z_buffer = ZBuffer::new(pixels, convention);
+        }
+    }
+
+    pub(super) fn sample_buffers(
+        &mut self,
+        sample: usize,
+    ) -> (&mut ZBuffer<D>, &mut StencilBuffer, &mut FrameBuffer<Color>) {
+        match sample {
+            0 => (
+                &mut self.z_buffer,
+                &mut self.stencil_buffer,
+                &mut self.frame_buffer,
+            ),
+            _ => {
+                let buffers = &mut self.samples[sample - 1];
+                (
+                    &mut buffers.z_buffer,
+                    &mut buffers.stencil_buffer,
+                    &mut buffers.frame_buffer,
+                )
+            }
+        }
+    }
+
+    // Hi-Z against every sample, a triangle is only hidden if it's hidden in all of them
+    pub(super) fn rejects_all(&self, inverse_w: f32, compare: DepthCompare) -> bool {
+        self.z_buffer.rejects_all(inverse_w, compare)
+            && self
+                .samples
+                .iter()
+                .all(|sample| sample.z_buffer.rejects_all(inverse_w, compare))
+    }
+
+    // Averages the samples into the tile's frame buffer
+    pub(super) fn resolve(&mut self) {
+        if self.samples.is_empty() {
+            return;
+        }
+
+        let count = self.msaa.samples() as u32;
+        for (index, color) in self.frame_buffer.frame_buffer.iter_mut().enumerate() {
+            let mut sum = [color.r, color.g, color.b, color.a].map(u32::from);
+            for sample in self.samples.iter() {
+                let Color { r, g, b, a } = sample.frame_buffer[index];
+                [r, g, b, a]
+                    .into_iter()
+                    .zip(sum.iter_mut())
+                    .for_each(|(channel, sum)| *sum += channel as u32);
+            }
+
+            let [r, g, b, a] = sum.map(|sum| ((sum + count / 2) / count) as u8);
+            *color = Color::rgba(r, g, b, a);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Vec2, Vec4};
+    use shared::vertex_parameters::VertexParameters;
+
+    use super::super::{
+        rasterizer::{EdgeStepperCombined, RenderTriangle},
+        Triangle,
+    };
+    use super::*;
+
+    // Samples covered in each pixel of the first stamp, by a triangle whose left edge is at x = 2.25
+    fn covered_samples(msaa: Msaa) -> [usize; 4] {
+        let triangle = RenderTriangle::setup(Triangle::<0> {
+            positions: [(2.25, 0.0), (64.0, 0.0), (2.25, 64.0)]
+                .map(|(x, y)| Vec4::new(x, y, 1.0, 1.0)),
+            parameters: [VertexParameters([]); 3],
+        });
+
+        let mut covered = [0; 4];
+        for &offset in msaa.sample_offsets() {
+            let stepper = EdgeStepperCombined::new(
+                &triangle,
+                Vec2::ZERO,
+                &[0, 1, 2, 3],
+                &[0; 4],
+                4,
+                1,
+                offset,
+            );
+            let mask = stepper.points_inside_triangle_mask().move_mask();
+            for (bit, covered) in covered.iter_mut().enumerate() {
+                *covered += (mask & 1 << bit != 0) as usize;
+            }
+        }
+        covered
+    }
+
+    #[test]
+    fn samples_partially_cover_pixels_on_edges() {
+        assert_eq!(covered_samples(Msaa::Off), [0, 0, 1, 1]);
+        // One of the samples is exactly on the left edge, which belongs to the triangle
+        assert_eq!(covered_samples(Msaa::X2), [0, 0, 2, 2]);
+        assert_eq!(covered_samples(Msaa::X4), [0, 0, 3, 4]);
+    }
+
+    #[test]
+    fn resolve_averages_the_samples() {
+        let mut tile = RenderTile::<f32>::new(0, 0, 32, 18, DepthConvention::default());
+        tile.set_msaa(Msaa::X2);
+
+        tile.frame_buffer[0] = Color::new(255, 255, 255);
+        tile.sample_buffers(1).2[0] = Color::rgba(0, 0, 0, 255);
+        tile.resolve();
+
+        assert_eq!(tile.frame_buffer[0], Color::new(128, 128, 128));
+        // Pixels no sample was drawn to stay cleared
+        assert_eq!(tile.frame_buffer[1], Color::default());
+    }
+}
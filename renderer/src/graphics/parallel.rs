@@ -0,0 +1,44 @@
+use std::{num::NonZeroUsize, sync::Mutex, thread};
+
+use super::{
+    command_buffer::{rasterize_tile, DrawCommand},
+    render_tile::RenderTile,
+    DepthFormat, RasterStats,
+};
+
+// Rasterizes the tiles across one worker thread per core. Tiles are handed out
+// one at a time as workers become free, as some tiles are much busier than others
+pub(super) fn rasterize_tiles<D: DepthFormat>(
+    tiles: &mut [RenderTile<D>],
+    draw_commands: &[Box<dyn DrawCommand<D>>],
+) -> RasterStats {
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let remaining = Mutex::new(tiles.iter_mut());
+
+    thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut stats = RasterStats::default();
+
+                    loop {
+                        // Taken in its own statement, so the lock is released before rasterizing
+                        let next = remaining.lock().unwrap().next();
+                        let Some(tile) = next else {
+                            break;
+                        };
+                        rasterize_tile(tile, draw_commands, &mut stats);
+                    }
+
+                    stats
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut stats = RasterStats::default();
+        for handle in handles {
+            stats += handle.join().unwrap();
+        }
+        stats
+    })
+}
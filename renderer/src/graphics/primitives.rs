@@ -0,0 +1,148 @@
+use glam::{Vec2, Vec3A, Vec4, Vec4Swizzles};
+use shared::vertex_parameters::VertexParameters;
+
+use crate::shaders::{PixelShader, VertexShader};
+
+use super::{
+    rasterizer::RenderTriangle, stats::Span, DepthFormat, Gpu, Line, RenderTarget, Triangle,
+};
+
+/// A vertex of a line or point, passed to the vertex shader as a mesh's vertex would be
+#[derive(Clone, Copy, Debug)]
+pub struct PrimitiveVertex<const IN: usize> {
+    pub position: Vec3A,
+    pub input: [f32; IN],
+}
+
+impl<const IN: usize> PrimitiveVertex<IN> {
+    pub fn new(position: Vec3A, input: [f32; IN]) -> Self {
+        Self { position, input }
+    }
+}
+
+impl<T: RenderTarget, D: DepthFormat> Gpu<T, D> {
+    /// Draws each pair of vertices as a line, `line_width` pixels wide in the render state.
+    /// Lines go through the same clipping, depth and stencil tests as triangles
+    pub fn render_lines<VS, const VSIN: usize, PS, const PSIN: usize>(
+        &mut self,
+        lines: &[[PrimitiveVertex<VSIN>; 2]],
+        vs: VS,
+        _ps: PS,
+    ) where
+        VS: VertexShader<VSIN, PSIN>,
+        PS: PixelShader<PSIN>,
+    {
+        let span = Span::start();
+        let mut triangles = Vec::new();
+
+        for (index, [a, b]) in lines.iter().enumerate() {
+            let a = vs.run(index * 2, &self.uniforms, a.position, a.input);
+            let b = vs.run(index * 2 + 1, &self.uniforms, b.position, b.input);
+
+            let line = Line {
+                positions: [a.position, b.position],
+                parameters: [a.parameters, b.parameters],
+            };
+            self.bin_line(line, &mut triangles);
+        }
+
+        self.record_draw::<PS, PSIN>(triangles, self.blend_mode);
+        self.stats.timings.geometry += span.elapsed();
+    }
+
+    /// Draws each vertex as a square, `point_size` pixels across in the render state
+    pub fn render_points<VS, const VSIN: usize, PS, const PSIN: usize>(
+        &mut self,
+        points: &[PrimitiveVertex<VSIN>],
+        vs: VS,
+        _ps: PS,
+    ) where
+        VS: VertexShader<VSIN, PSIN>,
+        PS: PixelShader<PSIN>,
+    {
+        let span = Span::start();
+        let mut triangles = Vec::new();
+
+        for (index, point) in points.iter().enumerate() {
+            let point = vs.run(index, &self.uniforms, point.position, point.input);
+            self.bin_point(point.position, point.parameters, &mut triangles);
+        }
+
+        self.record_draw::<PS, PSIN>(triangles, self.blend_mode);
+        self.stats.timings.geometry += span.elapsed();
+    }
+
+    // Lines are expanded into quads in screen space, which are binned as two triangles
+    pub(super) fn bin_line<const P: usize>(
+        &mut self,
+        line: Line<P>,
+        triangles: &mut Vec<RenderTriangle<P>>,
+    ) {
+        let Some(line) = self.clip_line(line) else {
+            return;
+        };
+        let [a, b] = line.positions.map(|position| self.clip_to_screen(position));
+        let [a_parameters, b_parameters] = line.parameters;
+
+        // Square caps, extended by half the width, so lines meet without gaps at their joints.
+        // Lines shorter than a pixel become squares
+        let half_width = self.render_state.line_width * 0.5;
+        let along = (b.xy() - a.xy()).try_normalize().unwrap_or(Vec2::X) * half_width;
+        let across = along.perp();
+
+        let corners = [
+            offset(a, -along - across),
+            offset(a, -along + across),
+            offset(b, along + across),
+            offset(b, along - across),
+        ];
+        let parameters = [a_parameters, a_parameters, b_parameters, b_parameters];
+        self.bin_quad(corners, parameters, triangles);
+    }
+
+    pub(super) fn bin_point<const P: usize>(
+        &mut self,
+        position: Vec4,
+        parameters: VertexParameters<P>,
+        triangles: &mut Vec<RenderTriangle<P>>,
+    ) {
+        if !self.point_is_visible(&position) {
+            return;
+        }
+        let center = self.clip_to_screen(position);
+        let half_size = self.render_state.point_size * 0.5;
+
+        let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| offset(center, Vec2::new(x, y) * half_size));
+        self.bin_quad(corners, [parameters; 4], triangles);
+    }
+
+    // Splits a quad in screen space into two triangles, given its corners in order around it
+    fn bin_quad<const P: usize>(
+        &mut self,
+        corners: [Vec4; 4],
+        parameters: [VertexParameters<P>; 4],
+        triangles: &mut Vec<RenderTriangle<P>>,
+    ) {
+        // The rasterizer expects the inside of each edge to be on its right
+        let [a, b, c] = [corners[0], corners[1], corners[2]].map(|corner| corner.xy());
+        let order = if (b - a).perp_dot(c - a) < 0.0 {
+            [[0, 3, 2], [0, 2, 1]]
+        } else {
+            [[0, 1, 2], [0, 2, 3]]
+        };
+
+        for indices in order {
+            let triangle = Triangle {
+                positions: indices.map(|i| corners[i]),
+                parameters: indices.map(|i| parameters[i]),
+            };
+            self.bin_triangle(RenderTriangle::setup(triangle), triangles);
+        }
+    }
+}
+
+// Moves a screen space position, keeping its depth
+fn offset(position: Vec4, by: Vec2) -> Vec4 {
+    Vec4::new(position.x + by.x, position.y + by.y, position.z, position.w)
+}
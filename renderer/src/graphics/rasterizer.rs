@@ -0,0 +1,1031 @@
+use std::array;
+
+use glam::{IVec2, Vec2, Vec3A, Vec3Swizzles, Vec4Swizzles};
+use shared::{types::Color, vertex_parameters::VertexParameters};
+use wide::{f32x4, i32x4, CmpGt, CmpLt};
+
+use crate::shaders::{Derivatives, PixelShader, VertexParametersSimd};
+
+use super::{
+    command_buffer::DrawCall,
+    msaa::{Msaa, MAX_SAMPLES},
+    render_tile::RenderTile,
+    BlendMode, DebugView, DepthFormat, FrameBuffer, RasterStats, Rasterizer, Triangle,
+};
+
+// TODO: Consider using a 2x2 tiled approach
+pub(super) const X_STEP_SIZE: usize = 4;
+pub(super) const Y_STEP_SIZE: usize = 1;
+
+pub(super) const X_STAMP_OFFSETS: [i32; 4] = [0, 1, 2, 3];
+pub(super) const Y_STAMP_OFFSETS: [i32; 4] = [0, 0, 0, 0];
+
+// Vertices are snapped to a fixed point grid with this many bits of sub-pixel precision
+const SUB_PIXEL_BITS: i32 = 4;
+const SUB_PIXEL_STEPS: i32 = 1 << SUB_PIXEL_BITS;
+
+// Pixels are sampled at their centers
+const PIXEL_CENTER: i32 = SUB_PIXEL_STEPS / 2;
+
+// Largest area in pixels a triangle can span before its edge equations overflow
+pub(super) const MAX_RASTER_AREA: f32 =
+    i32::MAX as f32 / (2 * SUB_PIXEL_STEPS * SUB_PIXEL_STEPS) as f32;
+
+pub struct EdgeStepperCombined {
+    double_triangle_area: i64,
+    one_over_triangle_2a: f32,
+
+    a_edge: EdgeStepper,
+    b_edge: EdgeStepper,
+    c_edge: EdgeStepper,
+
+    wa_row: i32x4,
+    wb_row: i32x4,
+    wc_row: i32x4,
+
+    wa: i32x4,
+    wb: i32x4,
+    wc: i32x4,
+}
+
+impl EdgeStepperCombined {
+    pub fn new<const P: usize>(
+        triangle: &RenderTriangle<P>,
+        top_left: Vec2,
+        initial_x_stamp: &[i32; 4],
+        initial_y_stamp: &[i32; 4],
+        x_step: i32,
+        y_step: i32,
+        sample_offset: IVec2,
+    ) -> Self {
+        // Local Setup, snapped to the sub-pixel grid
+        let a = to_fixed(triangle.a.xy());
+        let b = to_fixed(triangle.b);
+        let c = to_fixed(triangle.c);
+
+        // Where the first pixel is sampled, its center unless multisampling
+        let origin = (top_left.as_ivec2() << SUB_PIXEL_BITS) + PIXEL_CENTER + sample_offset;
+
+        // Initialize steppers
+        let double_triangle_area = double_triangle_area(a, b, c);
+        let one_over_triangle_2a = (double_triangle_area as f32).recip();
+        let (a_edge, wa_row) = EdgeStepper::initialize(
+            b,
+            c,
+            origin,
+            initial_x_stamp,
+            initial_y_stamp,
+            x_step,
+            y_step,
+        );
+        let (b_edge, wb_row) = EdgeStepper::initialize(
+            c,
+            a,
+            origin,
+            initial_x_stamp,
+            initial_y_stamp,
+            x_step,
+            y_step,
+        );
+        let (c_edge, wc_row) = EdgeStepper::initialize(
+            a,
+            b,
+            origin,
+            initial_x_stamp,
+            initial_y_stamp,
+            x_step,
+            y_step,
+        );
+
+        Self {
+            double_triangle_area,
+            one_over_triangle_2a,
+            a_edge,
+            b_edge,
+            c_edge,
+            wa_row,
+            wb_row,
+            wc_row,
+            wa: wa_row,
+            wb: wb_row,
+            wc: wc_row,
+        }
+    }
+
+    // Triangles can collapse, or flip, once snapped to the sub-pixel grid
+    pub fn is_degenerate(&self) -> bool {
+        self.double_triangle_area <= 0
+    }
+
+    pub fn step_x(&mut self) {
+        // Increment weights one step to the right
+        self.wa += self.a_edge.step_x;
+        self.wb += self.b_edge.step_x;
+        self.wc += self.c_edge.step_x;
+    }
+
+    pub fn reset_row(&mut self) {
+        self.wa = self.wa_row;
+        self.wb = self.wb_row;
+        self.wc = self.wc_row;
+    }
+
+    pub fn step_y(&mut self) {
+        // Increment weights one step down
+        self.wa_row += self.a_edge.step_y;
+        self.wb_row += self.b_edge.step_y;
+        self.wc_row += self.c_edge.step_y;
+    }
+
+    pub fn points_inside_triangle_mask(&self) -> i32x4 {
+        // If the pixel is inside the triangle (barycentric coordinates are non-negative)
+        // Pixels exactly on an edge were biased during setup, following the top-left rule.
+        // The sign bit is only clear in the combined weights when all of them are non-negative
+        (self.wa | self.wb | self.wc).cmp_gt(i32x4::splat(-1))
+    }
+
+    // The first and last pixels of the current row whose samples are inside the triangle, as
+    // offsets from the stepper's origin within first..=last, or None if the row misses it
+    fn row_span(&self, first: i32, last: i32) -> Option<(i32, i32)> {
+        let edges = [
+            (&self.wa_row, &self.a_edge),
+            (&self.wb_row, &self.b_edge),
+            (&self.wc_row, &self.c_edge),
+        ];
+
+        let (start, end) = edges
+            .into_iter()
+            .fold((first, last), |(start, end), (row, edge)| {
+                // The weight at the origin and its change per pixel
+                let weight = row.as_array_ref()[0];
+                let step = edge.step_x.as_array_ref()[0] / X_STEP_SIZE as i32;
+
+                // Inside where weight + step * x >= 0, bounding x on one side
+                match step.signum() {
+                    1 => (start.max(-weight.div_euclid(step)), end),
+                    -1 => (start, end.min(weight.div_euclid(-step))),
+                    _ if weight >= 0 => (start, end),
+                    _ => (last + 1, end),
+                }
+            });
+
+        (start <= end).then_some((start, end))
+    }
+
+    // Moves to the stamp this many steps right of the start of the row
+    fn seek_x(&mut self, steps: i32) {
+        let steps = i32x4::splat(steps);
+        self.wa = self.wa_row + self.a_edge.step_x * steps;
+        self.wb = self.wb_row + self.b_edge.step_x * steps;
+        self.wc = self.wc_row + self.c_edge.step_x * steps;
+    }
+
+    fn normalized_weights(&self) -> (f32x4, f32x4) {
+        // a's weight is skipped
+        (
+            self.wb.round_float() * self.one_over_triangle_2a,
+            self.wc.round_float() * self.one_over_triangle_2a,
+        )
+    }
+
+    // Change of the normalized weights from one pixel to the next on the right
+    fn normalized_weights_dx(&self) -> (f32, f32) {
+        let per_pixel = |edge: &EdgeStepper| {
+            (edge.step_x.as_array_ref()[0] / X_STEP_SIZE as i32) as f32 * self.one_over_triangle_2a
+        };
+        (per_pixel(&self.b_edge), per_pixel(&self.c_edge))
+    }
+}
+
+// Depths and parameters across a stamp, before the perspective divide
+#[derive(Clone)]
+struct Interpolants<const P: usize> {
+    depths: f32x4,
+    parameters: VertexParametersSimd<P>,
+}
+
+// Steps the weights, depths and parameters along a row's span a stamp at a time. They're set up
+// at the span's first stamp from the edge functions, then only added to, being linear in x
+struct SpanStepper<const P: usize, const SAMPLES: usize> {
+    weights: (f32x4, f32x4),
+    // At the pixel centers, where the parameters are interpolated
+    center: Interpolants<P>,
+    sample_depths: [f32x4; SAMPLES],
+
+    // Changes from one stamp to the next
+    weights_step: (f32x4, f32x4),
+    depth_step: f32x4,
+    parameters_step: VertexParametersSimd<P>,
+}
+
+impl<const P: usize, const SAMPLES: usize> SpanStepper<P, SAMPLES> {
+    fn new(triangle: &RenderTriangle<P>, (b_dx, c_dx): (f32, f32)) -> Self {
+        let [b_step, c_step] = [b_dx, c_dx].map(|dx| f32x4::splat(dx * X_STEP_SIZE as f32));
+        let RenderTriangle {
+            b_sub_a, c_sub_a, ..
+        } = triangle;
+
+        Self {
+            weights: Default::default(),
+            center: triangle.interpolate(Default::default()),
+            sample_depths: [f32x4::default(); SAMPLES],
+            weights_step: (b_step, c_step),
+            depth_step: b_sub_a.z * b_step + c_sub_a.z * c_step,
+            parameters_step: b_sub_a.parameters.clone() * b_step
+                + c_sub_a.parameters.clone() * c_step,
+        }
+    }
+
+    // Sets up the first stamp of a span, from the weights at its pixel centers and samples
+    fn start(
+        &mut self,
+        triangle: &RenderTriangle<P>,
+        center_weights: (f32x4, f32x4),
+        sample_weights: [(f32x4, f32x4); SAMPLES],
+    ) {
+        self.weights = center_weights;
+        self.center = triangle.interpolate(center_weights);
+        self.sample_depths = sample_weights.map(|weights| triangle.interpolate_depths(weights));
+    }
+
+    fn step(&mut self) {
+        self.weights.0 += self.weights_step.0;
+        self.weights.1 += self.weights_step.1;
+        self.center.depths += self.depth_step;
+        self.center.parameters = self.center.parameters.clone() + self.parameters_step.clone();
+        for depths in self.sample_depths.iter_mut() {
+            *depths += self.depth_step;
+        }
+    }
+}
+
+// Which pixels of a stamp a sample covers, and the depths there
+struct SampleCoverage {
+    mask: i32,
+    depths: f32x4,
+}
+
+impl<D: DepthFormat> RenderTile<D> {
+    // Only used without multisampling, as the binner tests the corner pixels' centers
+    pub(super) fn trivial_rasterize_triangle<PS, const PSIN: usize>(
+        &mut self,
+        draw: &DrawCall<PS, PSIN>,
+        mut triangle: RenderTriangle<PSIN>,
+        stats: &mut RasterStats,
+    ) where
+        PS: PixelShader<PSIN>,
+    {
+        // Triangle Setup
+        let top_left = Vec2::new(self.x as f32, self.y as f32);
+        let mut stepper = EdgeStepperCombined::new(
+            &triangle,
+            top_left,
+            &X_STAMP_OFFSETS,
+            &Y_STAMP_OFFSETS,
+            X_STEP_SIZE as i32,
+            Y_STEP_SIZE as i32,
+            IVec2::ZERO,
+        );
+
+        // Iterate over each pixel in the bounding box
+        for y in (0..self.height).step_by(Y_STEP_SIZE) {
+            // Reset to start of row.
+            stepper.reset_row();
+            for x in (0..self.stride).step_by(X_STEP_SIZE) {
+                // Normalize the weights
+                let weights = stepper.normalized_weights();
+                (triangle.b_sub_a.weight, triangle.c_sub_a.weight) = weights;
+
+                let coverage = SampleCoverage {
+                    mask: 0b1111,
+                    depths: triangle.interpolate_depths(weights),
+                };
+                stats.quads_tested += 1;
+                self.render_pixels(draw, x, y, &triangle, &[coverage], stats);
+
+                // One step right
+                stepper.step_x();
+            }
+            // One step down
+            stepper.step_y();
+        }
+    }
+
+    pub(super) fn rasterize_triangle<PS, const PSIN: usize>(
+        &mut self,
+        draw: &DrawCall<PS, PSIN>,
+        triangle: RenderTriangle<PSIN>,
+        stats: &mut RasterStats,
+    ) where
+        PS: PixelShader<PSIN>,
+    {
+        match (draw.render_state.rasterizer, self.msaa) {
+            (Rasterizer::Stamps, Msaa::Off) => {
+                self.rasterize_samples::<PS, PSIN, 1>(draw, triangle, stats)
+            }
+            (Rasterizer::Stamps, Msaa::X2) => {
+                self.rasterize_samples::<PS, PSIN, 2>(draw, triangle, stats)
+            }
+            (Rasterizer::Stamps, Msaa::X4) => {
+                self.rasterize_samples::<PS, PSIN, 4>(draw, triangle, stats)
+            }
+            (Rasterizer::Scanline, Msaa::Off) => {
+                self.rasterize_scanlines::<PS, PSIN, 1>(draw, triangle, stats)
+            }
+            (Rasterizer::Scanline, Msaa::X2) => {
+                self.rasterize_scanlines::<PS, PSIN, 2>(draw, triangle, stats)
+            }
+            (Rasterizer::Scanline, Msaa::X4) => {
+                self.rasterize_scanlines::<PS, PSIN, 4>(draw, triangle, stats)
+            }
+        }
+    }
+
+    fn rasterize_samples<PS, const PSIN: usize, const SAMPLES: usize>(
+        &mut self,
+        draw: &DrawCall<PS, PSIN>,
+        mut triangle: RenderTriangle<PSIN>,
+        stats: &mut RasterStats,
+    ) where
+        PS: PixelShader<PSIN>,
+    {
+        // Determine the bounding box of the triangle in tile space
+        let min_x = triangle.min_x.max(self.x as f32) as usize & !3;
+        let min_y = triangle.min_y.max(self.y as f32) as usize;
+        let max_x = triangle.max_x.min((self.x + self.stride - 1) as f32) as usize;
+        let max_y = triangle.max_y.min((self.y + self.height - 1) as f32) as usize;
+
+        // Triangle Setup, with a stepper for each sample
+        let top_left = Vec2::new(min_x as f32, min_y as f32);
+        let offsets = self.msaa.sample_offsets();
+        let new_stepper = |sample_offset| {
+            EdgeStepperCombined::new(
+                &triangle,
+                top_left,
+                &X_STAMP_OFFSETS,
+                &Y_STAMP_OFFSETS,
+                X_STEP_SIZE as i32,
+                Y_STEP_SIZE as i32,
+                sample_offset,
+            )
+        };
+        let mut steppers: [_; SAMPLES] = array::from_fn(|i| new_stepper(offsets[i]));
+
+        // Parameters are interpolated at the pixel centers,
+        // where the only sample is unless multisampling
+        let mut center = (SAMPLES > 1).then(|| new_stepper(IVec2::ZERO));
+
+        // Iterate over each pixel in the bounding box
+        for y in (min_y..=max_y).step_by(Y_STEP_SIZE) {
+            steppers
+                .iter_mut()
+                .chain(&mut center)
+                .for_each(|stepper| stepper.reset_row());
+            for x in (min_x..=max_x).step_by(X_STEP_SIZE) {
+                // See if any pixels extend out of the bb
+                let pixel_indices = i32x4::splat(x as i32) + i32x4::new(X_STAMP_OFFSETS);
+                let bb_valid_mask = pixel_indices.cmp_lt(i32x4::splat(max_x as i32 + 1));
+
+                // If the sample is inside the triangle (barycentric coordinates are non-negative)
+                stats.quads_tested += 1;
+                let masks = steppers.each_ref().map(|stepper| {
+                    (stepper.points_inside_triangle_mask() & bb_valid_mask).move_mask()
+                });
+
+                if masks.iter().any(|&mask| mask != 0) {
+                    // Normalize the weights
+                    let weights = steppers
+                        .each_ref()
+                        .map(|stepper| stepper.normalized_weights());
+                    (triangle.b_sub_a.weight, triangle.c_sub_a.weight) = match &center {
+                        Some(center) => center.normalized_weights(),
+                        None => weights[0],
+                    };
+
+                    let samples: [_; SAMPLES] = array::from_fn(|i| SampleCoverage {
+                        mask: masks[i],
+                        depths: triangle.interpolate_depths(weights[i]),
+                    });
+                    self.render_pixels(draw, x - self.x, y - self.y, &triangle, &samples, stats);
+                }
+
+                // One step right
+                steppers
+                    .iter_mut()
+                    .chain(&mut center)
+                    .for_each(|stepper| stepper.step_x());
+            }
+
+            // One step down
+            // Also resets the X's to start of row values
+            steppers
+                .iter_mut()
+                .chain(&mut center)
+                .for_each(|stepper| stepper.step_y());
+        }
+    }
+
+    // Walks the span of each row the triangle covers, only visiting the stamps within it.
+    // Coverage is found from the same fixed point edges as the stamps, so the pixels match.
+    // Each span's weights, depths and parameters are set up at its start, then stepped across.
+    // When multisampling each sample finds its own span, and the stamps cover all of them
+    fn rasterize_scanlines<PS, const PSIN: usize, const SAMPLES: usize>(
+        &mut self,
+        draw: &DrawCall<PS, PSIN>,
+        mut triangle: RenderTriangle<PSIN>,
+        stats: &mut RasterStats,
+    ) where
+        PS: PixelShader<PSIN>,
+    {
+        // Rows of the triangle within the tile
+        let min_y = triangle.min_y.max(self.y as f32) as usize;
+        let max_y = triangle.max_y.min((self.y + self.height - 1) as f32) as usize;
+        let last_x = self.stride as i32 - 1;
+
+        // Stepped from the left of the tile, so spans are in tile space
+        let top_left = Vec2::new(self.x as f32, min_y as f32);
+        let offsets = self.msaa.sample_offsets();
+        let new_stepper = |sample_offset| {
+            EdgeStepperCombined::new(
+                &triangle,
+                top_left,
+                &X_STAMP_OFFSETS,
+                &Y_STAMP_OFFSETS,
+                X_STEP_SIZE as i32,
+                Y_STEP_SIZE as i32,
+                sample_offset,
+            )
+        };
+        let mut steppers: [_; SAMPLES] = array::from_fn(|i| new_stepper(offsets[i]));
+
+        // Parameters are interpolated at the pixel centers,
+        // where the only sample is unless multisampling
+        let mut center = (SAMPLES > 1).then(|| new_stepper(IVec2::ZERO));
+        let mut span =
+            SpanStepper::<PSIN, SAMPLES>::new(&triangle, steppers[0].normalized_weights_dx());
+
+        for y in min_y..=max_y {
+            let spans = steppers
+                .each_ref()
+                .map(|stepper| stepper.row_span(0, last_x));
+            let covered = spans
+                .iter()
+                .flatten()
+                .copied()
+                .reduce(|(start, end), (first, last)| (start.min(first), end.max(last)));
+
+            if let Some((start, end)) = covered {
+                // Whole stamps across the spans, masking off the pixels outside of each
+                let first_stamp = start - start % X_STEP_SIZE as i32;
+                steppers
+                    .iter_mut()
+                    .chain(&mut center)
+                    .for_each(|stepper| stepper.seek_x(first_stamp / X_STEP_SIZE as i32));
+
+                let sample_weights = steppers
+                    .each_ref()
+                    .map(|stepper| stepper.normalized_weights());
+                let center_weights = match &center {
+                    Some(center) => center.normalized_weights(),
+                    None => sample_weights[0],
+                };
+                span.start(&triangle, center_weights, sample_weights);
+
+                for x in (first_stamp..=end).step_by(X_STEP_SIZE) {
+                    let pixels = i32x4::splat(x) + i32x4::new(X_STAMP_OFFSETS);
+                    let samples: [_; SAMPLES] = array::from_fn(|i| SampleCoverage {
+                        mask: spans[i].map_or(0, |(start, end)| {
+                            (pixels.cmp_gt(i32x4::splat(start - 1))
+                                & pixels.cmp_lt(i32x4::splat(end + 1)))
+                            .move_mask()
+                        }),
+                        depths: span.sample_depths[i],
+                    });
+
+                    (triangle.b_sub_a.weight, triangle.c_sub_a.weight) = span.weights;
+                    triangle.stepped = Some(span.center.clone());
+                    stats.quads_tested += 1;
+                    self.render_pixels(draw, x as usize, y - self.y, &triangle, &samples, stats);
+
+                    span.step();
+                }
+            }
+
+            steppers
+                .iter_mut()
+                .chain(&mut center)
+                .for_each(|stepper| stepper.step_y());
+        }
+    }
+
+    // Tests the samples of a stamp, shading the pixels once if any sample passes
+    fn render_pixels<PS, const PSIN: usize>(
+        &mut self,
+        draw: &DrawCall<PS, PSIN>,
+        x: usize,
+        y: usize,
+        triangle: &RenderTriangle<PSIN>,
+        samples: &[SampleCoverage],
+        stats: &mut RasterStats,
+    ) where
+        PS: PixelShader<PSIN>,
+    {
+        let DrawCall {
+            blend_mode,
+            render_state,
+            ..
+        } = draw;
+
+        // Calculate the pixel's index
+        let pixel_index = (y * self.stride) + x;
+        let stencil = render_state.stencil_test.then_some(&render_state.stencil);
+        let compare = render_state.depth_compare();
+
+        // Test each sample against its own buffers
+        let mut passed = [0; MAX_SAMPLES];
+        let mut covered = 0;
+        for (sample, coverage) in samples.iter().enumerate() {
+            let (z_buffer, stencil_buffer, _) = self.sample_buffers(sample);
+
+            // Stencil testing comes first, its ops also depend on the depth test
+            let stencil_passed = match stencil {
+                Some(stencil) => stencil_buffer.test(pixel_index, coverage.mask, stencil),
+                None => coverage.mask,
+            };
+            covered |= stencil_passed;
+
+            // Perform depth testing
+            passed[sample] = z_buffer.test(pixel_index, coverage.depths, stencil_passed, compare);
+            if let Some(stencil) = stencil {
+                let depth_failed = stencil_passed & !passed[sample];
+                stencil_buffer.apply(pixel_index, depth_failed, stencil.depth_fail, stencil);
+            }
+        }
+
+        let any_passed = passed.iter().fold(0, |any, passed| any | passed);
+        stats.depth_rejected += (covered & !any_passed).count_ones() as usize;
+        if draw.debug_view == DebugView::Overdraw {
+            for bit in 0..4 {
+                if (any_passed & 1 << bit) != 0 {
+                    let count = &mut self.overdraw[pixel_index + bit];
+                    *count = count.saturating_add(1);
+                }
+            }
+        }
+
+        // Nothing to shade, such as when rendering a shadow map or a depth pre-pass.
+        // Alpha tested draws still shade, as discarded fragments mustn't write depth
+        let discards = matches!(blend_mode, BlendMode::AlphaTest(_));
+        let colors = if PS::DEPTH_ONLY || (!render_state.color_write && !discards) {
+            None
+        } else {
+            stats.shaded += any_passed.count_ones() as usize;
+            Some(self.shade_pixels(draw, triangle, any_passed))
+        };
+
+        for (sample, coverage) in samples.iter().enumerate() {
+            let (z_buffer, stencil_buffer, frame_buffer) = self.sample_buffers(sample);
+            let drawn = match &colors {
+                Some(colors) => {
+                    blend_pixels(frame_buffer, draw, pixel_index, colors, passed[sample])
+                }
+                None => passed[sample],
+            };
+
+            if render_state.depth_write && blend_mode.writes_depth() {
+                z_buffer.write(pixel_index, coverage.depths, drawn);
+            }
+            if let Some(stencil) = stencil {
+                stencil_buffer.apply(pixel_index, drawn, stencil.pass, stencil);
+            }
+        }
+    }
+
+    // Runs the pixel shader for the pixels set in the mask, at their centers
+    fn shade_pixels<PS, const PSIN: usize>(
+        &self,
+        draw: &DrawCall<PS, PSIN>,
+        triangle: &RenderTriangle<PSIN>,
+        mask: i32,
+    ) -> [Color; 4]
+    where
+        PS: PixelShader<PSIN>,
+    {
+        let mut colors = [Color::default(); 4];
+
+        // Continue if any pass the depth test
+        if mask > 0 {
+            // Stepped along a span, or interpolated from the weights
+            let Interpolants { depths, parameters } = match &triangle.stepped {
+                Some(stepped) => stepped.clone(),
+                None => triangle.interpolate((triangle.b_sub_a.weight, triangle.c_sub_a.weight)),
+            };
+            let ps_params = parameters * depths.recip();
+            let derivatives = triangle.derivatives();
+
+            for (bit, color) in colors.iter_mut().enumerate() {
+                if (mask & 1 << bit) != 0 {
+                    // Pun the pixel shader
+                    let params = ps_params.extract(bit);
+                    *color =
+                        draw.debug_view
+                            .shade::<PS, PSIN>(&draw.uniforms, params, &derivatives);
+                }
+            }
+        }
+
+        colors
+    }
+}
+
+// Blends the colors of the pixels set in the mask into the frame buffer,
+// returning the mask of those which weren't discarded
+fn blend_pixels<PS, const PSIN: usize>(
+    frame_buffer: &mut FrameBuffer<Color>,
+    draw: &DrawCall<PS, PSIN>,
+    pixel_index: usize,
+    colors: &[Color; 4],
+    mask: i32,
+) -> i32 {
+    // Tracks which pixels weren't discarded
+    let mut drawn = 0;
+
+    for (bit, fragment_color) in colors.iter().enumerate() {
+        if (mask & 1 << bit) != 0 {
+            let pixel = &mut frame_buffer[pixel_index + bit];
+            if let Some(color) = draw.blend_mode.blend(*fragment_color, *pixel) {
+                if draw.render_state.color_write {
+                    *pixel = color;
+                }
+                drawn |= 1 << bit;
+            }
+        }
+    }
+
+    drawn
+}
+
+#[derive(Clone)]
+pub(super) struct RenderTriangle<const P: usize> {
+    pub(super) a: Vec3A,
+    pub(super) b: Vec2,
+    pub(super) c: Vec2,
+    a_params: VertexParametersSimd<P>,
+    b_sub_a: RenderVertex<P>,
+    c_sub_a: RenderVertex<P>,
+    pub(super) min_x: f32,
+    pub(super) max_x: f32,
+    pub(super) min_y: f32,
+    pub(super) max_y: f32,
+    pub(super) min_depth: f32,
+    pub(super) max_depth: f32,
+    depth_dx: f32,
+    depth_dy: f32,
+    // Set by the scanline rasterizer for the current stamp, instead of the weights
+    stepped: Option<Interpolants<P>>,
+}
+
+impl<const P: usize> RenderTriangle<P> {
+    pub(crate) fn setup(triangle: Triangle<P>) -> Self {
+        let a = triangle.positions[0];
+        let b = triangle.positions[1];
+        let c = triangle.positions[2];
+
+        let min_x = a.x.min(b.x).min(c.x);
+        let max_x = a.x.max(b.x).max(c.x);
+
+        let min_y = a.y.min(b.y).min(c.y);
+        let max_y = a.y.max(b.y).max(c.y);
+
+        // Depth is interpolated linearly, so it never exceeds the vertices' range
+        let min_depth = a.w.min(b.w).min(c.w);
+        let max_depth = a.w.max(b.w).max(c.w);
+
+        // Screen space gradients of the depth plane
+        let ab = b.xyw() - a.xyw();
+        let ac = c.xyw() - a.xyw();
+        let double_area = ab.x * ac.y - ab.y * ac.x;
+        let depth_dx = (ab.z * ac.y - ac.z * ab.y) / double_area;
+        let depth_dy = (ac.z * ab.x - ab.z * ac.x) / double_area;
+
+        // Screen space gradients of the barycentric weights
+        let b_weight_gradient = Vec2::new(ac.y, -ac.x) / double_area;
+        let c_weight_gradient = Vec2::new(-ab.y, ab.x) / double_area;
+
+        let a_params = triangle.parameters[0] * a.w;
+        let b_params = (triangle.parameters[1] * b.w) - a_params;
+        let c_params = (triangle.parameters[2] * c.w) - a_params;
+
+        Self {
+            a: a.xyw().into(),
+            b: b.xy(),
+            c: c.xy(),
+            a_params: VertexParametersSimd::splat(&a_params),
+            b_sub_a: RenderVertex::new(b.w - a.w, b_weight_gradient, b_params),
+            c_sub_a: RenderVertex::new(c.w - a.w, c_weight_gradient, c_params),
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            min_depth,
+            max_depth,
+            depth_dx,
+            depth_dy,
+            stepped: None,
+        }
+    }
+
+    // Interpolates depth at the given weights of b and c, using simplified formula
+    fn interpolate_depths(&self, (b_weight, c_weight): (f32x4, f32x4)) -> f32x4 {
+        self.a.z + (self.b_sub_a.z * b_weight) + (self.c_sub_a.z * c_weight)
+    }
+
+    // Sums the parameters to complete interpolation at the given weights, using simplified formula
+    fn interpolate(&self, weights: (f32x4, f32x4)) -> Interpolants<P> {
+        let (b_weight, c_weight) = weights;
+        Interpolants {
+            depths: self.interpolate_depths(weights),
+            parameters: self.a_params.clone()
+                + (self.b_sub_a.parameters.clone() * b_weight)
+                + (self.c_sub_a.parameters.clone() * c_weight),
+        }
+    }
+
+    // Conservative nearest depth of the triangle within a screen space rectangle
+    pub(super) fn max_depth_in(&self, min: Vec2, max: Vec2) -> f32 {
+        let center = (min + max) * 0.5;
+        let half_extents = (max - min) * 0.5;
+
+        let center_depth = self.a.z
+            + self.depth_dx * (center.x - self.a.x)
+            + self.depth_dy * (center.y - self.a.y);
+        let plane_max = center_depth
+            + self.depth_dx.abs() * half_extents.x
+            + self.depth_dy.abs() * half_extents.y;
+
+        // Degenerate gradients produce NaN, which min ignores
+        self.max_depth.min(plane_max)
+    }
+
+    // Interpolates the parameters one pixel right of, and one pixel below,
+    // the first pixel of the current stamp, using the spare SIMD lanes
+    fn derivatives(&self) -> Derivatives<P> {
+        let offsets = |vertex: &RenderVertex<P>| {
+            let weight = vertex.weight.as_array_ref()[0];
+            f32x4::splat(weight)
+                + f32x4::new([0.0, vertex.weight_gradient.x, vertex.weight_gradient.y, 0.0])
+        };
+        let b_weight = offsets(&self.b_sub_a);
+        let c_weight = offsets(&self.c_sub_a);
+
+        let depths = self.a.z + (self.b_sub_a.z * b_weight) + (self.c_sub_a.z * c_weight);
+        let samples = (self.a_params.clone()
+            + (self.b_sub_a.parameters.clone() * b_weight)
+            + (self.c_sub_a.parameters.clone() * c_weight))
+            * depths.recip();
+
+        let [origin, right, below] = [0, 1, 2].map(|lane| samples.extract(lane));
+        Derivatives {
+            dx: std::array::from_fn(|i| right[i] - origin[i]),
+            dy: std::array::from_fn(|i| below[i] - origin[i]),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct RenderVertex<const P: usize> {
+    z: f32,
+    weight: f32x4,
+    weight_gradient: Vec2,
+    parameters: VertexParametersSimd<P>,
+}
+
+impl<const P: usize> RenderVertex<P> {
+    fn new(z: f32, weight_gradient: Vec2, parameters: VertexParameters<P>) -> Self {
+        Self {
+            z,
+            weight: f32x4::default(),
+            weight_gradient,
+            parameters: VertexParametersSimd::splat(&parameters),
+        }
+    }
+}
+
+struct EdgeStepper {
+    step_x: i32x4,
+    step_y: i32x4,
+}
+
+impl EdgeStepper {
+    fn initialize(
+        v0: IVec2,
+        v1: IVec2,
+        origin: IVec2,
+        initial_x_stamp: &[i32; 4],
+        initial_y_stamp: &[i32; 4],
+        x_step: i32,
+        y_step: i32,
+    ) -> (Self, i32x4) {
+        // Edge setup
+        let a = v0.y - v1.y;
+        let b = v1.x - v0.x;
+
+        // Step Deltas, in whole pixels
+        let step_x = i32x4::splat(a * x_step * SUB_PIXEL_STEPS);
+        let step_y = i32x4::splat(b * y_step * SUB_PIXEL_STEPS);
+
+        let out = Self { step_x, step_y };
+
+        // Pixels exactly on a top or left edge belong to this triangle,
+        // others are pushed just outside so shared edges are only drawn once
+        let bias = if is_top_left_edge(v0, v1) { 0 } else { -1 };
+
+        // Edge function weights at the sample point of each pixel in the initial block,
+        // evaluated relative to v0 to keep the products small
+        let weight = std::array::from_fn(|i| {
+            let x = origin.x + (initial_x_stamp[i] << SUB_PIXEL_BITS) - v0.x;
+            let y = origin.y + (initial_y_stamp[i] << SUB_PIXEL_BITS) - v0.y;
+            (a as i64 * x as i64 + b as i64 * y as i64 + bias) as i32
+        });
+
+        (out, i32x4::new(weight))
+    }
+}
+
+fn to_fixed(v: Vec2) -> IVec2 {
+    (v * SUB_PIXEL_STEPS as f32).round().as_ivec2()
+}
+
+fn double_triangle_area(v0: IVec2, v1: IVec2, v2: IVec2) -> i64 {
+    (v1.x - v0.x) as i64 * (v2.y - v0.y) as i64 - (v1.y - v0.y) as i64 * (v2.x - v0.x) as i64
+}
+
+// Screen space has y pointing down, and triangles are wound so that
+// the inside of each edge is to its right
+fn is_top_left_edge(v0: IVec2, v1: IVec2) -> bool {
+    is_top_edge(v0, v1) | is_left_edge(v0, v1)
+}
+
+fn is_top_edge(v0: IVec2, v1: IVec2) -> bool {
+    // Check if the edge is horizontal
+    let horizontal = v0.y == v1.y;
+
+    // Check if the edge is going right (positive X axis)
+    let going_right = v1.x > v0.x;
+
+    horizontal & going_right
+}
+
+fn is_left_edge(v0: IVec2, v1: IVec2) -> bool {
+    // Check if the edge is going up
+    v1.y < v0.y
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec4;
+
+    use super::*;
+
+    // Rasterizes a fan of triangles sharing edges, which should cover a
+    // 16x16 square with each pixel drawn exactly once
+    #[test]
+    fn shared_edges_are_watertight() {
+        const SIZE: usize = 16;
+
+        let center = Vec2::new(8.0, 8.0);
+        let perimeter = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(5.3, 0.0),
+            Vec2::new(16.0, 0.0),
+            Vec2::new(16.0, 9.7),
+            Vec2::new(16.0, 16.0),
+            Vec2::new(2.2, 16.0),
+            Vec2::new(0.0, 16.0),
+            Vec2::new(0.0, 4.1),
+        ];
+
+        let mut coverage = [0; SIZE * SIZE];
+
+        for (i, &b) in perimeter.iter().enumerate() {
+            let c = perimeter[(i + 1) % perimeter.len()];
+            let triangle = RenderTriangle::setup(Triangle::<0> {
+                positions: [center, b, c].map(|v| Vec4::new(v.x, v.y, 1.0, 1.0)),
+                parameters: [VertexParameters([]); 3],
+            });
+
+            let mut stepper = EdgeStepperCombined::new(
+                &triangle,
+                Vec2::ZERO,
+                &X_STAMP_OFFSETS,
+                &Y_STAMP_OFFSETS,
+                X_STEP_SIZE as i32,
+                Y_STEP_SIZE as i32,
+                IVec2::ZERO,
+            );
+
+            for y in (0..SIZE).step_by(Y_STEP_SIZE) {
+                stepper.reset_row();
+                for x in (0..SIZE).step_by(X_STEP_SIZE) {
+                    let mask = stepper.points_inside_triangle_mask().move_mask();
+                    for bit in 0..4 {
+                        if mask & 1 << bit != 0 {
+                            coverage[x + bit + y * SIZE] += 1;
+                        }
+                    }
+                    stepper.step_x();
+                }
+                stepper.step_y();
+            }
+        }
+
+        assert!(coverage.iter().all(|&count| count == 1), "{coverage:?}");
+    }
+
+    // Spans found where rows cross the edges cover the same pixels the stamps test inside
+    #[test]
+    fn row_spans_match_the_stamps() {
+        const SIZE: i32 = 32;
+
+        let triangles = [
+            [
+                Vec2::new(1.3, 0.2),
+                Vec2::new(30.6, 7.9),
+                Vec2::new(4.1, 31.5),
+            ],
+            [
+                Vec2::new(16.0, 2.0),
+                Vec2::new(17.5, 29.25),
+                Vec2::new(15.0, 30.0),
+            ],
+            [
+                Vec2::new(-8.0, 4.0),
+                Vec2::new(40.0, 4.0),
+                Vec2::new(8.0, 12.5),
+            ],
+        ];
+
+        for positions in triangles {
+            let triangle = RenderTriangle::setup(Triangle::<0> {
+                positions: positions.map(|v| Vec4::new(v.x, v.y, 1.0, 1.0)),
+                parameters: [VertexParameters([]); 3],
+            });
+            let mut stepper = EdgeStepperCombined::new(
+                &triangle,
+                Vec2::ZERO,
+                &X_STAMP_OFFSETS,
+                &Y_STAMP_OFFSETS,
+                X_STEP_SIZE as i32,
+                Y_STEP_SIZE as i32,
+                IVec2::ZERO,
+            );
+
+            for y in 0..SIZE {
+                let mut inside = Vec::new();
+                stepper.reset_row();
+                for x in (0..SIZE).step_by(X_STEP_SIZE) {
+                    let mask = stepper.points_inside_triangle_mask().move_mask();
+                    inside.extend((0..4).filter(|bit| mask & 1 << bit != 0).map(|bit| x + bit));
+                    stepper.step_x();
+                }
+
+                let span = stepper.row_span(0, SIZE - 1);
+                let expected = inside.first().zip(inside.last()).map(|(&a, &b)| (a, b));
+                assert_eq!(span, expected, "{positions:?}, row {y}");
+                stepper.step_y();
+            }
+        }
+    }
+
+    // Texture coordinates spanning 64x32 pixels change by 1/64 and 1/32 per pixel
+    #[test]
+    fn derivatives_match_the_screen_space_mapping() {
+        let mut triangle = RenderTriangle::setup(Triangle::<2> {
+            positions: [
+                Vec4::new(0.0, 0.0, 1.0, 1.0),
+                Vec4::new(64.0, 0.0, 1.0, 1.0),
+                Vec4::new(0.0, 32.0, 1.0, 1.0),
+            ],
+            parameters: [
+                VertexParameters([0.0, 0.0]),
+                VertexParameters([1.0, 0.0]),
+                VertexParameters([0.0, 1.0]),
+            ],
+        });
+
+        let stepper = EdgeStepperCombined::new(
+            &triangle,
+            Vec2::new(8.0, 4.0),
+            &X_STAMP_OFFSETS,
+            &Y_STAMP_OFFSETS,
+            X_STEP_SIZE as i32,
+            Y_STEP_SIZE as i32,
+            IVec2::ZERO,
+        );
+        (triangle.b_sub_a.weight, triangle.c_sub_a.weight) = stepper.normalized_weights();
+
+        let (ddx, ddy) = triangle.derivatives().pair(0);
+        assert!((ddx - Vec2::new(1.0 / 64.0, 0.0)).length() < 1e-5, "{ddx}");
+        assert!((ddy - Vec2::new(0.0, 1.0 / 32.0)).length() < 1e-5, "{ddy}");
+    }
+}
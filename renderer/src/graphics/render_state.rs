@@ -0,0 +1,182 @@
+use wide::{f32x4, CmpEq, CmpGe, CmpGt, CmpLe, CmpLt, CmpNe};
+
+use super::StencilState;
+
+/// Which fragments pass the depth test, by their distance compared to what is already stored
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum DepthCompare {
+    Never,
+    #[default]
+    Nearer,
+    NearerOrEqual,
+    Equal,
+    NotEqual,
+    FartherOrEqual,
+    Farther,
+    Always,
+}
+
+impl DepthCompare {
+    // Lanes set where the fragment passes. Depths are stored as 1/w, so larger values are nearer
+    pub(super) fn compare(self, fragment: f32x4, stored: f32x4) -> f32x4 {
+        match self {
+            Self::Never => f32x4::ZERO,
+            Self::Nearer => fragment.cmp_gt(stored),
+            Self::NearerOrEqual => fragment.cmp_ge(stored),
+            Self::Equal => fragment.cmp_eq(stored),
+            Self::NotEqual => fragment.cmp_ne(stored),
+            Self::FartherOrEqual => fragment.cmp_le(stored),
+            Self::Farther => fragment.cmp_lt(stored),
+            Self::Always => f32x4::splat(bytemuck::cast(u32::MAX)),
+        }
+    }
+
+    // Whether a triangle no nearer than `nearest` can't pass anywhere the
+    // stored depths are at least `farthest`, so it can be skipped entirely
+    pub(super) fn rejects_all(self, nearest: f32, farthest: f32) -> bool {
+        match self {
+            Self::Never => true,
+            Self::Nearer => nearest <= farthest,
+            Self::NearerOrEqual => nearest < farthest,
+            _ => false,
+        }
+    }
+}
+
+/// How the triangles of meshes are drawn. Only filled triangles are back face culled
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum PolygonMode {
+    #[default]
+    Fill,
+    // Lines along each edge, as a wireframe. Edges shared by two triangles are drawn by both
+    Line,
+    // A point at each vertex, drawn once by every triangle using it
+    Point,
+}
+
+/// How filled triangles are turned into pixels. Both produce the same pixels, and share
+/// the triangle setup and pixel shading, so can be swapped to compare their speed
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum Rasterizer {
+    // Tests the edge functions at every 4x1 stamp of the triangle's bounds within a tile
+    #[default]
+    Stamps,
+    // Finds where each row crosses the edges, then steps the weights, depths and parameters
+    // along the span between from their values at its start. Skips the empty stamps of thin
+    // and small triangles. With MSAA each sample finds its own span
+    Scanline,
+}
+
+/// Per draw rasterization, depth, stencil and color state, copied into each draw when it's
+/// recorded. The defaults are a regular opaque draw, testing and writing both depth and color
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RenderState {
+    pub polygon_mode: PolygonMode,
+    pub rasterizer: Rasterizer,
+    // Sizes of lines and points in pixels
+    pub line_width: f32,
+    pub point_size: f32,
+    // When off every fragment passes, as if the compare was Always
+    pub depth_test: bool,
+    pub depth_write: bool,
+    pub depth_compare: DepthCompare,
+    // When off the pixel shader only runs if it could discard fragments
+    pub color_write: bool,
+    // When off the stencil is neither tested nor written
+    pub stencil_test: bool,
+    pub stencil: StencilState,
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        Self {
+            polygon_mode: PolygonMode::default(),
+            rasterizer: Rasterizer::default(),
+            line_width: 1.0,
+            point_size: 1.0,
+            depth_test: true,
+            depth_write: true,
+            depth_compare: DepthCompare::default(),
+            color_write: true,
+            stencil_test: false,
+            stencil: StencilState::default(),
+        }
+    }
+}
+
+impl RenderState {
+    // Writes depth without color, for depth pre-passes
+    pub fn depth_only() -> Self {
+        Self {
+            color_write: false,
+            ..Self::default()
+        }
+    }
+
+    // Tests against depth without writing it, for decals and skyboxes
+    pub fn depth_read_only() -> Self {
+        Self {
+            depth_write: false,
+            ..Self::default()
+        }
+    }
+
+    // Draws over everything, ignoring depth, for overlays such as a HUD
+    pub fn overlay() -> Self {
+        Self {
+            depth_test: false,
+            depth_write: false,
+            ..Self::default()
+        }
+    }
+
+    // Draws the edges of meshes' triangles, for debugging their topology
+    pub fn wireframe() -> Self {
+        Self {
+            polygon_mode: PolygonMode::Line,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_stencil(mut self, stencil: StencilState) -> Self {
+        self.stencil_test = true;
+        self.stencil = stencil;
+        self
+    }
+
+    pub(super) fn depth_compare(self) -> DepthCompare {
+        if self.depth_test {
+            self.depth_compare
+        } else {
+            DepthCompare::Always
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Which of a nearer, equal and farther fragment pass against a stored depth
+    fn passes(compare: DepthCompare) -> [bool; 3] {
+        let stored = f32x4::splat(0.5);
+        let fragments = f32x4::new([1.0, 0.5, 0.25, 0.0]);
+        let mask = compare.compare(fragments, stored).move_mask();
+        [0, 1, 2].map(|bit| mask & 1 << bit != 0)
+    }
+
+    #[test]
+    fn compares_by_distance() {
+        assert_eq!(passes(DepthCompare::Never), [false, false, false]);
+        assert_eq!(passes(DepthCompare::Nearer), [true, false, false]);
+        assert_eq!(passes(DepthCompare::NearerOrEqual), [true, true, false]);
+        assert_eq!(passes(DepthCompare::Equal), [false, true, false]);
+        assert_eq!(passes(DepthCompare::NotEqual), [true, false, true]);
+        assert_eq!(passes(DepthCompare::FartherOrEqual), [false, true, true]);
+        assert_eq!(passes(DepthCompare::Farther), [false, false, true]);
+        assert_eq!(passes(DepthCompare::Always), [true, true, true]);
+
+        let overlay = RenderState::overlay();
+        assert_eq!(overlay.depth_compare(), DepthCompare::Always);
+    }
+}
@@ -0,0 +1,230 @@
+use shared::types::Color;
+
+/// The destination for everything the [`Gpu`](super::Gpu) draws. Tiles are rendered
+/// as [`Color`]s, which the target encodes into its own pixel format once the frame is complete.
+pub trait RenderTarget {
+    type Pixel: Copy + Default;
+
+    fn encode(&self, color: Color) -> Self::Pixel;
+
+    /// How many evenly spaced values each of the red, green and blue channels can hold,
+    /// which sets how strongly colors are dithered
+    fn channel_levels(&self) -> [u16; 3] {
+        [256; 3]
+    }
+
+    /// The color as it will be displayed, used to measure the error when dithering
+    fn quantize(&self, color: Color) -> Color {
+        let [r, g, b] = self.channel_levels();
+        Color::rgba(
+            from_level(to_level(color.r, r), r),
+            from_level(to_level(color.g, g), g),
+            from_level(to_level(color.b, b), b),
+            color.a,
+        )
+    }
+}
+
+// Rounds an 8-bit channel to the nearest of a smaller number of levels
+fn to_level(value: u8, levels: u16) -> u16 {
+    let max = levels as u32 - 1;
+    ((value as u32 * max + 127) / u8::MAX as u32) as u16
+}
+
+fn from_level(level: u16, levels: u16) -> u8 {
+    let max = levels as u32 - 1;
+    ((level as u32 * u8::MAX as u32 + max / 2) / max) as u8
+}
+
+/// Plain 8-bit per channel RGB output, useful for headless rendering
+#[derive(Clone, Copy, Default)]
+pub struct Rgb8;
+
+impl RenderTarget for Rgb8 {
+    type Pixel = [u8; 3];
+
+    fn encode(&self, color: Color) -> Self::Pixel {
+        [color.r, color.g, color.b]
+    }
+}
+
+/// Plain 8-bit per channel RGBA output, alpha is always opaque
+#[derive(Clone, Copy, Default)]
+pub struct Rgba8;
+
+impl RenderTarget for Rgba8 {
+    type Pixel = [u8; 4];
+
+    fn encode(&self, color: Color) -> Self::Pixel {
+        [color.r, color.g, color.b, u8::MAX]
+    }
+}
+
+/// 5-bit red, 5-bit green and 4-bit blue packed into the low 14 bits,
+/// the layout of the `5r5g4b_color.gce` palettes
+#[derive(Clone, Copy, Default)]
+pub struct R5G5B4;
+
+impl R5G5B4 {
+    // Each palette holds 64 colors, so the top 8 bits select the palette
+    pub fn palette_index(pixel: u16) -> u8 {
+        (pixel >> 6) as u8
+    }
+
+    pub fn color_index(pixel: u16) -> u8 {
+        (pixel & 0b11_1111) as u8
+    }
+}
+
+impl RenderTarget for R5G5B4 {
+    type Pixel = u16;
+
+    fn encode(&self, color: Color) -> Self::Pixel {
+        let [r, g, b] = self.channel_levels();
+        (to_level(color.r, r) << 9) | (to_level(color.g, g) << 4) | to_level(color.b, b)
+    }
+
+    fn channel_levels(&self) -> [u16; 3] {
+        [32, 32, 16]
+    }
+}
+
+/// 16-bit RGB with 5-bit red, 6-bit green and 5-bit blue
+#[derive(Clone, Copy, Default)]
+pub struct Rgb565;
+
+impl RenderTarget for Rgb565 {
+    type Pixel = u16;
+
+    fn encode(&self, color: Color) -> Self::Pixel {
+        let [r, g, b] = self.channel_levels();
+        (to_level(color.r, r) << 11) | (to_level(color.g, g) << 5) | to_level(color.b, b)
+    }
+
+    fn channel_levels(&self) -> [u16; 3] {
+        [32, 64, 32]
+    }
+}
+
+/// Outputs the index of the nearest color in an arbitrary palette of up to 256 colors.
+/// Searching the palette for every pixel is slow, so the nearest color for each
+/// 5-bit per channel RGB value is looked up from a table built up front
+pub struct IndexedPalette {
+    palette: Box<[Color]>,
+    lookup: Box<[u8]>,
+}
+
+impl IndexedPalette {
+    const BITS: u32 = 5;
+    const LEVELS: usize = 1 << Self::BITS;
+
+    pub fn new(palette: &[Color]) -> Self {
+        assert!(
+            (1..=256).contains(&palette.len()),
+            "Palettes must have between 1 and 256 colors"
+        );
+
+        let lookup = (0..Self::LEVELS.pow(3))
+            .map(|index| {
+                // Compare from the center of each table entry
+                let channel = |shift: usize| {
+                    let level = (index >> (shift * Self::BITS as usize)) % Self::LEVELS;
+                    ((level << (8 - Self::BITS)) + (1 << (7 - Self::BITS))) as i32
+                };
+                let (r, g, b) = (channel(2), channel(1), channel(0));
+
+                let distance = |color: &Color| {
+                    let (dr, dg, db) = (color.r as i32 - r, color.g as i32 - g, color.b as i32 - b);
+                    dr * dr + dg * dg + db * db
+                };
+
+                (0..palette.len())
+                    .min_by_key(|&i| distance(&palette[i]))
+                    .unwrap() as u8
+            })
+            .collect();
+
+        Self {
+            palette: palette.into(),
+            lookup,
+        }
+    }
+}
+
+impl RenderTarget for IndexedPalette {
+    type Pixel = u8;
+
+    fn encode(&self, color: Color) -> Self::Pixel {
+        let shift = 8 - Self::BITS;
+        let index = ((color.r as usize >> shift) << (Self::BITS * 2))
+            | ((color.g as usize >> shift) << Self::BITS)
+            | (color.b as usize >> shift);
+        self.lookup[index]
+    }
+
+    // Assumes the palette is spread evenly over the color cube
+    fn channel_levels(&self) -> [u16; 3] {
+        let levels = (self.palette.len() as f32).cbrt().round() as u16;
+        [levels.max(2); 3]
+    }
+
+    fn quantize(&self, color: Color) -> Color {
+        Color {
+            a: color.a,
+            ..self.palette[self.encode(color) as usize]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_5r5g4b_into_palette_and_color_indices() {
+        // Green rounds to level 22, blue to level 9
+        let pixel = R5G5B4.encode(Color::new(255, 180, 150));
+
+        assert_eq!(R5G5B4::palette_index(pixel), (31 << 3) | (22 >> 2));
+        assert_eq!(R5G5B4::color_index(pixel), ((22 & 0b11) << 4) | 9);
+    }
+
+    #[test]
+    fn packs_5r5g4b_to_the_nearest_levels() {
+        // Truncating would give red 0, green 31 and blue 0
+        let pixel = R5G5B4.encode(Color::new(5, 250, 9));
+
+        assert_eq!(pixel, (1 << 9) | (30 << 4) | 1);
+    }
+
+    #[test]
+    fn packs_rgb565() {
+        assert_eq!(Rgb565.encode(Color::new(255, 255, 255)), u16::MAX);
+        assert_eq!(Rgb565.encode(Color::new(0, 4, 0)), 1 << 5);
+        assert_eq!(Rgb565.encode(Color::new(0, 1, 0)), 0);
+    }
+
+    #[test]
+    fn quantize_matches_the_encoded_levels() {
+        assert_eq!(Rgb8.quantize(Color::new(1, 2, 3)), Color::new(1, 2, 3));
+        assert_eq!(
+            R5G5B4.quantize(Color::new(255, 0, 9)),
+            Color::new(255, 0, 17)
+        );
+    }
+
+    #[test]
+    fn indexed_palette_picks_the_nearest_color() {
+        let palette = IndexedPalette::new(&[
+            Color::new(0, 0, 0),
+            Color::new(255, 0, 0),
+            Color::new(0, 0, 255),
+            Color::new(255, 255, 255),
+        ]);
+
+        assert_eq!(palette.encode(Color::new(20, 10, 5)), 0);
+        assert_eq!(palette.encode(Color::new(200, 40, 30)), 1);
+        assert_eq!(palette.encode(Color::new(30, 60, 180)), 2);
+        assert_eq!(palette.encode(Color::new(220, 200, 240)), 3);
+    }
+}
@@ -0,0 +1,163 @@
+// The default tile size, which divides every 16:9 resolution from 128 x 72 up to 1920 x 1080
+pub const DEFAULT_TILE_WIDTH: usize = 32;
+pub const DEFAULT_TILE_HEIGHT: usize = 18;
+
+use glam::{Vec2, Vec2Swizzles, Vec3Swizzles};
+
+use shared::types::Color;
+
+use super::{
+    command_buffer::{BinCounts, BinnedTriangle},
+    msaa::{Msaa, SampleBuffers},
+    rasterizer::{RenderTriangle, X_STEP_SIZE},
+    DepthConvention, DepthFormat, FrameBuffer, StencilBuffer, ZBuffer,
+};
+
+pub(super) struct RenderTile<D: DepthFormat> {
+    pub(super) x: usize, // Left point
+    pub(super) y: usize, // Top point
+    // Tiles on the right and bottom edges of the screen may be cut short
+    pub(super) width: usize,
+    pub(super) height: usize,
+    // Pixels in each row of the buffers, the width rounded up to whole stamps.
+    // The pixels past the width are rasterized, but never shown
+    pub(super) stride: usize,
+    pub(super) z_buffer: ZBuffer<D>,
+    pub(super) stencil_buffer: StencilBuffer,
+    pub(super) frame_buffer: FrameBuffer<Color>,
+    pub(super) msaa: Msaa,
+    // Buffers of every sample after the first, empty unless multisampling
+    pub(super) samples: Vec<SampleBuffers<D>>,
+    pub(super) bin: Vec<BinnedTriangle>,
+    // Kept for the debug views
+    pub(super) overdraw: FrameBuffer<u8>,
+    pub(super) bin_counts: BinCounts,
+}
+
+impl<D: DepthFormat> RenderTile<D> {
+    pub fn new(
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        convention: DepthConvention,
+    ) -> Self {
+        let stride = width.next_multiple_of(X_STEP_SIZE);
+        let pixels = stride * height;
+
+        Self {
+            x,
+            y,
+            width,
+            height,
+            stride,
+            z_buffer: ZBuffer::new(pixels, convention),
+            stencil_buffer: StencilBuffer::new(pixels),
+            frame_buffer: FrameBuffer::new(pixels),
+            msaa: Msaa::default(),
+            samples: Vec::new(),
+            bin: Vec::new(),
+            overdraw: FrameBuffer::new(pixels),
+            bin_counts: BinCounts::default(),
+        }
+    }
+
+    // Pixels in each of the tile's buffers, including those past its width
+    pub(super) fn pixels(&self) -> usize {
+        self.stride * self.height
+    }
+
+    // The shown pixels' indices in the buffers, with their positions within the tile
+    pub(super) fn shown_pixels(&self) -> impl Iterator<Item = (usize, usize, usize)> {
+        let (width, stride) = (self.width, self.stride);
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (y * stride + x, x, y)))
+    }
+
+    pub(super) fn triangle_edges_intersect_aabb<const P: usize>(
+        &self,
+        triangle: &RenderTriangle<P>,
+    ) -> bool {
+        let aabb_min = Vec2::new(self.x as f32, self.y as f32);
+        // Out to the far sides of the last pixels, which may be covered by slivers past their corners
+        let aabb_max = Vec2::new((self.x + self.width) as f32, (self.y + self.height) as f32);
+
+        let ab = line_intersects_aabb(triangle.a.xy(), triangle.b.xy(), aabb_min, aabb_max);
+        let bc = line_intersects_aabb(triangle.b.xy(), triangle.c.xy(), aabb_min, aabb_max);
+        let ca = line_intersects_aabb(triangle.c.xy(), triangle.a.xy(), aabb_min, aabb_max);
+
+        ab || bc || ca
+    }
+}
+
+// Helper function to check if a line segment intersects with an AABB
+fn line_intersects_aabb(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> bool {
+    // Check if any part of the line segment intersects with the AABB's sides
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+
+    // Check for intersection with the left and right sides of the AABB
+    let mut t_min = (min.x - a.x) / dx;
+    let mut t_max = (max.x - a.x) / dx;
+
+    if dx < 0.0 {
+        std::mem::swap(&mut t_min, &mut t_max);
+    }
+
+    // Check for intersection with the top and bottom sides of the AABB
+    let mut t3 = (min.y - a.y) / dy;
+    let mut t4 = (max.y - a.y) / dy;
+
+    if dy < 0.0 {
+        std::mem::swap(&mut t3, &mut t4);
+    }
+
+    t_min = t_min.max(t3);
+    t_max = t_max.min(t4);
+
+    t_max >= t_min && t_max >= 0.0 && t_min <= 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec4;
+    use shared::vertex_parameters::VertexParameters;
+
+    use super::*;
+    use crate::graphics::Triangle;
+
+    fn sliver(positions: [Vec2; 3]) -> RenderTriangle<0> {
+        RenderTriangle::setup(Triangle::<0> {
+            positions: positions.map(|v| Vec4::new(v.x, v.y, 1.0, 1.0)),
+            parameters: [VertexParameters([]); 3],
+        })
+    }
+
+    // Slivers inside the last column or row cover those pixels' centers, but miss the centers
+    // at the tile's corners, so only their edges bin them.
+    // Bounded by the last pixels' near sides instead of their far sides, these were dropped
+    #[test]
+    fn slivers_on_the_far_sides_are_binned() {
+        let tile = RenderTile::<f32>::new(32, 18, 32, 18, DepthConvention::default());
+
+        let last_column = sliver([
+            Vec2::new(63.2, 20.0),
+            Vec2::new(63.8, 20.0),
+            Vec2::new(63.5, 33.0),
+        ]);
+        let last_row = sliver([
+            Vec2::new(36.0, 35.2),
+            Vec2::new(58.0, 35.5),
+            Vec2::new(36.0, 35.8),
+        ]);
+        assert!(tile.triangle_edges_intersect_aabb(&last_column));
+        assert!(tile.triangle_edges_intersect_aabb(&last_row));
+
+        // Past the far sides they belong to the next tiles
+        let next_column = sliver([
+            Vec2::new(64.2, 20.0),
+            Vec2::new(64.8, 20.0),
+            Vec2::new(64.5, 33.0),
+        ]);
+        assert!(!tile.triangle_edges_intersect_aabb(&next_column));
+    }
+}
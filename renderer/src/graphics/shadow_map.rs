@@ -0,0 +1,133 @@
+use glam::{Mat4, Vec3A};
+
+use super::{DepthFormat, Gpu, RenderTarget};
+
+// Offset along the view direction of the light before a surface shadows itself, in world units
+const DEFAULT_BIAS: f32 = 0.05;
+
+/// The nearest surfaces seen from a light, rendered by [`Gpu::render_shadow_map`].
+/// Lit pixel shaders use it to shadow the first light of their uniforms.
+pub struct ShadowMap {
+    width: usize,
+    height: usize,
+    // 1/w of the nearest surface at each pixel, converted back from the depth buffer
+    depths: Box<[f32]>,
+    view_projection: Mat4,
+    pub bias: f32,
+}
+
+impl ShadowMap {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Fraction of the light which reaches the position, filtered
+    // by testing the 3x3 texels around it (percentage closer filtering)
+    pub fn visibility(&self, position: Vec3A) -> f32 {
+        let clip = self.view_projection * position.extend(1.0);
+
+        // Behind the light, where nothing was rendered
+        if clip.w <= 0.0 {
+            return 1.0;
+        }
+
+        // The same mapping as the geometry pipeline's clip to screen space
+        let x = (clip.x / clip.w + 1.0) * (self.width as f32 / 2.0);
+        let y = (1.0 - clip.y / clip.w) * (self.height as f32 / 2.0);
+        let (x, y) = (x.floor() as isize, y.floor() as isize);
+
+        let mut lit = 0;
+        for offset_y in -1..=1 {
+            for offset_x in -1..=1 {
+                lit += self.is_lit(x + offset_x, y + offset_y, clip.w) as u32;
+            }
+        }
+
+        lit as f32 / 9.0
+    }
+
+    fn is_lit(&self, x: isize, y: isize, distance: f32) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return true;
+        }
+
+        // Texels where nothing was drawn are still at the reset depth
+        let depth = self.depths[y as usize * self.width + x as usize];
+        depth <= 0.0 || distance <= depth.recip() + self.bias
+    }
+}
+
+impl<T: RenderTarget, D: DepthFormat> Gpu<T, D> {
+    /// Renders a depth only pass from a light's point of view, drawn by `draw` with the view
+    /// and projection swapped in. Draws should use the [`DepthOnly`](crate::shaders::DepthOnly)
+    /// pixel shader, and projections must be perspective, as shadows are tested with 1/w.
+    /// This reuses the frame's tiles, so it must come before anything else is drawn,
+    /// and resets the frame once the map is taken
+    pub fn render_shadow_map(
+        &mut self,
+        view: Mat4,
+        projection: Mat4,
+        draw: impl FnOnce(&mut Self),
+    ) -> ShadowMap {
+        assert!(
+            self.draw_commands.is_empty(),
+            "Shadow maps must be rendered before anything else is drawn"
+        );
+
+        let camera = (self.uniforms.view, self.uniforms.projection);
+        (self.uniforms.view, self.uniforms.projection) = (view, projection);
+
+        draw(self);
+        self.rasterize_tiles();
+
+        let depths = self.stitch_depths(|z_buffer, index| z_buffer.inverse_w(index));
+
+        (self.uniforms.view, self.uniforms.projection) = camera;
+        self.reset_frame();
+
+        ShadowMap {
+            width: self.screen_width,
+            height: self.screen_height,
+            depths,
+            view_projection: projection * view,
+            bias: DEFAULT_BIAS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filtering_softens_the_edges_of_shadows() {
+        // Every position is 1 unit from the light, the left half is covered by something nearer
+        let (width, height) = (4, 4);
+        let depths = (0..width * height)
+            .map(|i| {
+                if i % width < 2 {
+                    2.0
+                } else {
+                    f32::NEG_INFINITY
+                }
+            })
+            .collect();
+        let shadow_map = ShadowMap {
+            width,
+            height,
+            depths,
+            view_projection: Mat4::IDENTITY,
+            bias: DEFAULT_BIAS,
+        };
+
+        // Texel centers across the middle row, from left to right
+        let visibility = [-0.75, -0.25, 0.25, 0.75]
+            .map(|x| shadow_map.visibility(Vec3A::new(x, -0.25, 0.0)) * 9.0);
+
+        assert_eq!(visibility, [3.0, 3.0, 6.0, 9.0]);
+    }
+}
@@ -0,0 +1,91 @@
+use std::{ops::AddAssign, time::Duration};
+
+use super::DepthRejectStats;
+
+/// Counts of the work done by each stage of a frame, and how long the stages took.
+/// Reset at the start of each frame, the raster counts and timings are only
+/// complete after [`Gpu::generate_frame_buffer`](super::Gpu::generate_frame_buffer)
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RenderStats {
+    pub geometry: GeometryStats,
+    pub raster: RasterStats,
+    pub timings: StageTimings,
+}
+
+/// Triangles through the vertex, culling, clipping and binning stages
+#[derive(Clone, Copy, Default, Debug)]
+pub struct GeometryStats {
+    // Triangles of the meshes drawn, lines and points aren't counted
+    pub submitted: usize,
+    pub backface_culled: usize,
+    // Entirely outside of the view, or clipped down to nothing
+    pub clipped_away: usize,
+    // Triangles which crossed the guard band or near / far planes, by how many
+    // triangles were left after clipping them
+    pub clipped_to_one: usize,
+    pub clipped_to_two: usize,
+    pub clipped_to_more: usize,
+    // Triangles which reached the binner and weren't degenerate, including those of lines and points
+    pub binned: usize,
+    // Tile-triangle pairs, where the triangle covers the whole tile, or only part of it
+    pub trivial_tiles: usize,
+    pub partial_tiles: usize,
+}
+
+/// Work done by the raster stage, summed across every tile
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RasterStats {
+    pub hi_z: DepthRejectStats,
+    // 4 pixel stamps tested against the triangles' edges
+    pub quads_tested: usize,
+    // Pixels covered by a triangle, but hidden by the depth test
+    pub depth_rejected: usize,
+    // Pixels the pixel shader ran for
+    pub shaded: usize,
+}
+
+impl AddAssign for RasterStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.hi_z += rhs.hi_z;
+        self.quads_tested += rhs.quads_tested;
+        self.depth_rejected += rhs.depth_rejected;
+        self.shaded += rhs.shaded;
+    }
+}
+
+/// Time spent in each stage, only measured with the `timings` feature on native targets.
+/// The geometry stage is summed across every draw
+#[derive(Clone, Copy, Default, Debug)]
+pub struct StageTimings {
+    pub geometry: Duration,
+    pub raster: Duration,
+    // Resolving, dithering and copying the tiles into the frame buffer
+    pub resolve: Duration,
+}
+
+// Measures a span of a stage, wasm32 has no clock so it always reads zero there
+pub(super) struct Span {
+    #[cfg(all(feature = "timings", not(target_arch = "wasm32")))]
+    start: std::time::Instant,
+}
+
+impl Span {
+    pub(super) fn start() -> Self {
+        Self {
+            #[cfg(all(feature = "timings", not(target_arch = "wasm32")))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    pub(super) fn elapsed(&self) -> Duration {
+        #[cfg(all(feature = "timings", not(target_arch = "wasm32")))]
+        {
+            self.start.elapsed()
+        }
+
+        #[cfg(not(all(feature = "timings", not(target_arch = "wasm32"))))]
+        {
+            Duration::ZERO
+        }
+    }
+}
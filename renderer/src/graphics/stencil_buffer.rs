@@ -0,0 +1,208 @@
+/// Compares the draw's reference value against the stored stencil value,
+/// as `reference <compare> stored`, both masked by the read mask
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum StencilCompare {
+    Never,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+    #[default]
+    Always,
+}
+
+impl StencilCompare {
+    fn passes(self, reference: u8, stored: u8) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Less => reference < stored,
+            Self::LessOrEqual => reference <= stored,
+            Self::Equal => reference == stored,
+            Self::NotEqual => reference != stored,
+            Self::GreaterOrEqual => reference >= stored,
+            Self::Greater => reference > stored,
+            Self::Always => true,
+        }
+    }
+}
+
+/// What happens to the stored stencil value after the tests
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum StencilOp {
+    #[default]
+    Keep,
+    Zero,
+    // Stores the reference value
+    Replace,
+    // Clamped to 0 -> 255
+    Increment,
+    Decrement,
+    IncrementWrap,
+    DecrementWrap,
+    Invert,
+}
+
+impl StencilOp {
+    fn apply(self, stored: u8, reference: u8) -> u8 {
+        match self {
+            Self::Keep => stored,
+            Self::Zero => 0,
+            Self::Replace => reference,
+            Self::Increment => stored.saturating_add(1),
+            Self::Decrement => stored.saturating_sub(1),
+            Self::IncrementWrap => stored.wrapping_add(1),
+            Self::DecrementWrap => stored.wrapping_sub(1),
+            Self::Invert => !stored,
+        }
+    }
+}
+
+/// The stencil test and the ops run on its outcome, set per draw through the render state
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StencilState {
+    pub compare: StencilCompare,
+    pub reference: u8,
+    // Bits compared by the test
+    pub read_mask: u8,
+    // Bits changed by the ops
+    pub write_mask: u8,
+    // The stencil test failed
+    pub fail: StencilOp,
+    // The stencil test passed, but the depth test failed
+    pub depth_fail: StencilOp,
+    // Both tests passed, and the fragment wasn't discarded
+    pub pass: StencilOp,
+}
+
+impl Default for StencilState {
+    fn default() -> Self {
+        Self {
+            compare: StencilCompare::default(),
+            reference: 0,
+            read_mask: u8::MAX,
+            write_mask: u8::MAX,
+            fail: StencilOp::default(),
+            depth_fail: StencilOp::default(),
+            pass: StencilOp::default(),
+        }
+    }
+}
+
+impl StencilState {
+    // Writes the reference value wherever the draw is visible, such as to mark a selection
+    pub fn write(reference: u8) -> Self {
+        Self {
+            reference,
+            pass: StencilOp::Replace,
+            ..Self::default()
+        }
+    }
+
+    // Only draws where the stored value matches the compare, such as within a portal
+    pub fn test(compare: StencilCompare, reference: u8) -> Self {
+        Self {
+            compare,
+            reference,
+            ..Self::default()
+        }
+    }
+
+    // Whether pixels which fail the depth test can still change the stencil
+    pub(super) fn writes_on_depth_fail(&self) -> bool {
+        self.fail != StencilOp::Keep || self.depth_fail != StencilOp::Keep
+    }
+}
+
+/// 8 bit stencil values for a tile, cleared to 0 each frame
+pub struct StencilBuffer {
+    pub stencil_buffer: Box<[u8]>,
+}
+
+impl StencilBuffer {
+    pub fn new(pixels: usize) -> Self {
+        Self {
+            stencil_buffer: vec![0; pixels].into_boxed_slice(),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.stencil_buffer.fill(0);
+    }
+
+    // Returns the bit mask of the pixels which pass the test,
+    // running the fail op on those in the mask which don't
+    pub fn test(&mut self, pixel_index: usize, mask: i32, state: &StencilState) -> i32 {
+        let mut passed = 0;
+
+        for bit in 0..4 {
+            let stored = self.stencil_buffer[pixel_index + bit];
+            if (mask & 1 << bit) != 0
+                && state
+                    .compare
+                    .passes(state.reference & state.read_mask, stored & state.read_mask)
+            {
+                passed |= 1 << bit;
+            }
+        }
+
+        self.apply(pixel_index, mask & !passed, state.fail, state);
+        passed
+    }
+
+    // Runs the op on the pixels set in the bit mask, only changing the bits of the write mask
+    pub fn apply(&mut self, pixel_index: usize, mask: i32, op: StencilOp, state: &StencilState) {
+        if op == StencilOp::Keep {
+            return;
+        }
+
+        for bit in 0..4 {
+            if (mask & 1 << bit) != 0 {
+                let stored = &mut self.stencil_buffer[pixel_index + bit];
+                let value = op.apply(*stored, state.reference);
+                *stored = (*stored & !state.write_mask) | (value & state.write_mask);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_then_masks_a_selection() {
+        let mut stencil = StencilBuffer::new(4);
+
+        // The selected object covers the middle two pixels
+        let mark = StencilState::write(1);
+        let passed = stencil.test(0, 0b0110, &mark);
+        stencil.apply(0, passed, mark.pass, &mark);
+        assert_eq!(*stencil.stencil_buffer, [0, 1, 1, 0]);
+
+        // An outline drawn over every pixel only passes around the selection
+        let outline = StencilState::test(StencilCompare::NotEqual, 1);
+        assert_eq!(stencil.test(0, 0b1111, &outline), 0b1001);
+    }
+
+    #[test]
+    fn ops_respect_the_write_mask() {
+        let mut stencil = StencilBuffer::new(4);
+        stencil.stencil_buffer = Box::new([0b1010_1010, 255, 0, 7]);
+
+        let state = StencilState {
+            write_mask: 0b0000_1111,
+            fail: StencilOp::Invert,
+            ..StencilState::test(StencilCompare::Never, 0)
+        };
+        assert_eq!(stencil.test(0, 0b1111, &state), 0);
+        assert_eq!(
+            *stencil.stencil_buffer,
+            [0b1010_0101, 0b1111_0000, 0b0000_1111, 8]
+        );
+
+        stencil.apply(0, 0b0110, StencilOp::Increment, &StencilState::default());
+        assert_eq!(stencil.stencil_buffer[1..3], [0b1111_0001, 0b0001_0000]);
+    }
+}
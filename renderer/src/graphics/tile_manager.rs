@@ -0,0 +1,81 @@
+use std::ops::{Index, IndexMut};
+
+use super::{msaa::SampleBuffers, render_tile::RenderTile, DepthConvention, DepthFormat};
+
+pub(super) struct TileManager<D: DepthFormat> {
+    pub(super) tiles: Box<[RenderTile<D>]>,
+    pub(super) tile_count_horizontal: usize,
+    pub(super) tile_count_vertical: usize,
+    tile_width: usize,
+    tile_height: usize,
+}
+
+impl<D: DepthFormat> Index<usize> for TileManager<D> {
+    type Output = RenderTile<D>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.tiles[index]
+    }
+}
+
+impl<D: DepthFormat> IndexMut<usize> for TileManager<D> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.tiles[index]
+    }
+}
+
+impl<D: DepthFormat> TileManager<D> {
+    // Tiles on the right and bottom edges are cut short when the screen isn't a multiple of the tile size
+    pub fn new(
+        screen_width: usize,
+        screen_height: usize,
+        tile_width: usize,
+        tile_height: usize,
+        convention: DepthConvention,
+    ) -> Self {
+        let tile_count_horizontal = screen_width.div_ceil(tile_width);
+        let tile_count_vertical = screen_height.div_ceil(tile_height);
+        let total_tile_count = tile_count_horizontal * tile_count_vertical;
+
+        let tiles = (0..total_tile_count)
+            .map(|i| {
+                let x = (i % tile_count_horizontal) * tile_width;
+                let y = (i / tile_count_horizontal) * tile_height;
+                let width = tile_width.min(screen_width - x);
+                let height = tile_height.min(screen_height - y);
+
+                RenderTile::new(x, y, width, height, convention)
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        Self {
+            tiles,
+            tile_count_horizontal,
+            tile_count_vertical,
+            tile_width,
+            tile_height,
+        }
+    }
+
+    // The size of every tile, besides those cut short on the edges
+    pub fn w(&self) -> usize {
+        self.tile_width
+    }
+
+    pub fn h(&self) -> usize {
+        self.tile_height
+    }
+
+    pub fn reset_frame(&mut self) {
+        self.tiles.iter_mut().for_each(|tile| {
+            tile.frame_buffer.clear();
+            tile.z_buffer.clear();
+            tile.stencil_buffer.clear();
+            tile.samples.iter_mut().for_each(SampleBuffers::clear);
+            tile.bin.clear();
+            tile.overdraw.clear();
+            tile.bin_counts = Default::default();
+        });
+    }
+}
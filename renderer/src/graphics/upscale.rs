@@ -0,0 +1,96 @@
+use shared::types::Color;
+
+use super::RenderTarget;
+
+/// How a frame rendered below the output resolution is stretched to fill it,
+/// see [`Gpu::set_render_scale`](super::Gpu::set_render_scale)
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum Upscale {
+    // Each output pixel takes the color of the rendered pixel under its center
+    #[default]
+    Nearest,
+    // Blends the 4 rendered pixels around each output pixel's center, smoother but blurrier
+    Bilinear,
+}
+
+impl Upscale {
+    // Stretches the rendered colors across the output, encoding them for the target
+    pub(super) fn apply<T: RenderTarget>(
+        self,
+        target: &T,
+        source: &[Color],
+        (source_width, source_height): (usize, usize),
+        output: &mut [T::Pixel],
+        output_width: usize,
+    ) {
+        let output_height = output.len() / output_width;
+        let x_ratio = source_width as f32 / output_width as f32;
+        let y_ratio = source_height as f32 / output_height as f32;
+
+        for (y, row) in output.chunks_exact_mut(output_width).enumerate() {
+            // Position of the output pixel's center on the source, in source pixels
+            let source_y = (y as f32 + 0.5) * y_ratio;
+
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let source_x = (x as f32 + 0.5) * x_ratio;
+
+                let color = match self {
+                    Self::Nearest => {
+                        let x = (source_x as usize).min(source_width - 1);
+                        let y = (source_y as usize).min(source_height - 1);
+                        source[y * source_width + x]
+                    }
+                    Self::Bilinear => bilinear(
+                        source,
+                        (source_width, source_height),
+                        source_x - 0.5,
+                        source_y - 0.5,
+                    ),
+                };
+                *pixel = target.encode(color);
+            }
+        }
+    }
+}
+
+// Samples between pixel centers, clamping to the edges of the source
+fn bilinear(source: &[Color], (width, height): (usize, usize), x: f32, y: f32) -> Color {
+    let (x, y) = (
+        x.clamp(0.0, (width - 1) as f32),
+        y.clamp(0.0, (height - 1) as f32),
+    );
+    let (left, top) = (x as usize, y as usize);
+    let (right, bottom) = ((left + 1).min(width - 1), (top + 1).min(height - 1));
+    let (tx, ty) = (x - left as f32, y - top as f32);
+
+    let lerp = |a: u8, b: u8, t: f32| a as f32 + (b as f32 - a as f32) * t;
+    let channel = |get: fn(&Color) -> u8| {
+        let at = |x, y| get(&source[y * width + x]);
+        let top_row = lerp(at(left, top), at(right, top), tx);
+        let bottom_row = lerp(at(left, bottom), at(right, bottom), tx);
+        (top_row + (bottom_row - top_row) * ty).round() as u8
+    };
+
+    Color::rgba(
+        channel(|c| c.r),
+        channel(|c| c.g),
+        channel(|c| c.b),
+        channel(|c| c.a),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bilinear_blends_between_pixel_centers() {
+        let source = [Color::new(0, 0, 0), Color::new(200, 100, 50)];
+
+        assert_eq!(bilinear(&source, (2, 1), 0.0, 0.0), source[0]);
+        assert_eq!(bilinear(&source, (2, 1), 0.5, 0.0), Color::new(100, 50, 25));
+        // Clamped past the edges
+        assert_eq!(bilinear(&source, (2, 1), 1.5, 0.0), source[1]);
+        assert_eq!(bilinear(&source, (2, 1), -0.5, 0.0), source[0]);
+    }
+}
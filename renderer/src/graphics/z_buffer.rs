@@ -0,0 +1,228 @@
+use std::array;
+
+use wide::f32x4;
+
+use super::{DepthCompare, DepthConvention, DepthFormat};
+
+// Bounds before anything has been drawn, nothing can be rejected against them
+const NO_BOUND: f32 = f32::NEG_INFINITY;
+
+/// Depth buffer for a tile, stored in the format `D` following a depth convention.
+/// Depths are given to it as the 1/w interpolated by the rasterizer
+pub struct ZBuffer<D: DepthFormat> {
+    pub z_buffer: Box<[D]>,
+    convention: DepthConvention,
+    // Maps 1/w to depth, see DepthConvention::mapping
+    scale: f32,
+    offset: f32,
+    cleared: D,
+
+    // Conservative bounds of the stored depths, the farthest is at most the smallest
+    // nearness in the buffer, and the nearest at least the largest. Nearness is the
+    // depth, negated for standard depth, so larger values are nearer either way
+    farthest: f32,
+    nearest: f32,
+
+    // Number of pixels still at the cleared value
+    uncovered: usize,
+}
+
+impl<D: DepthFormat> ZBuffer<D> {
+    pub fn new(pixels: usize, convention: DepthConvention) -> Self {
+        let (scale, offset) = convention.mapping();
+        let cleared = D::store(convention.far_depth());
+
+        Self {
+            z_buffer: vec![cleared; pixels].into_boxed_slice(),
+            convention,
+            scale,
+            offset,
+            cleared,
+            farthest: NO_BOUND,
+            nearest: NO_BOUND,
+            uncovered: pixels,
+        }
+    }
+
+    pub fn convention(&self) -> DepthConvention {
+        self.convention
+    }
+
+    // Clears the Z buffer by setting all values to the far plane
+    pub(crate) fn clear(&mut self) {
+        self.z_buffer.fill(self.cleared);
+        self.farthest = NO_BOUND;
+        self.nearest = NO_BOUND;
+        self.uncovered = self.z_buffer.len();
+    }
+
+    pub fn farthest(&self) -> f32 {
+        self.farthest
+    }
+
+    pub fn nearest(&self) -> f32 {
+        self.nearest
+    }
+
+    // The stored depth converted back to 1/w, which is 0 at an infinite far plane
+    pub fn inverse_w(&self, index: usize) -> f32 {
+        (self.z_buffer[index].load() - self.offset) / self.scale
+    }
+
+    // Nothing has been drawn to the pixel since it was cleared, or it was drawn at the far plane
+    pub fn is_cleared(&self, index: usize) -> bool {
+        self.z_buffer[index] == self.cleared
+    }
+
+    // Depths as they would be stored, ordered so larger values are nearer
+    fn nearness(&self, inverse_w: f32x4) -> f32x4 {
+        let depths = D::quantize(inverse_w * self.scale + self.offset);
+        self.depth_nearness(depths)
+    }
+
+    fn depth_nearness(&self, depths: f32x4) -> f32x4 {
+        if self.convention.is_reversed() {
+            depths
+        } else {
+            -depths
+        }
+    }
+
+    // Whether nothing of a triangle, at most as near as the 1/w given,
+    // can pass the compare anywhere in the tile
+    pub(crate) fn rejects_all(&self, inverse_w: f32, compare: DepthCompare) -> bool {
+        let nearest = self.nearness(f32x4::splat(inverse_w)).as_array_ref()[0];
+        compare.rejects_all(nearest, self.farthest)
+    }
+
+    // Every pixel has been drawn at least as near as this 1/w,
+    // so nothing stored can be any farther away
+    pub(crate) fn cover(&mut self, inverse_w: f32) {
+        self.uncovered = 0;
+        let nearness = self.nearness(f32x4::splat(inverse_w)).as_array_ref()[0];
+        self.farthest = self.farthest.max(nearness);
+    }
+
+    // Returns a bit mask of the pixels which pass the comparison
+    // against the stored values, and therefore should be drawn
+    pub fn test(
+        &self,
+        pixel_index: usize,
+        inverse_w: f32x4,
+        mask: i32,
+        compare: DepthCompare,
+    ) -> i32 {
+        let stored = f32x4::new(array::from_fn(|i| self.z_buffer[pixel_index + i].load()));
+
+        compare
+            .compare(self.nearness(inverse_w), self.depth_nearness(stored))
+            .move_mask()
+            & mask
+    }
+
+    // Stores the depths of the pixels set in the bit mask, once they have been drawn
+    pub fn write(&mut self, pixel_index: usize, inverse_w: f32x4, mask: i32) {
+        let depths = D::quantize(inverse_w * self.scale + self.offset);
+        let nearness = self.depth_nearness(depths);
+
+        for bit in 0..4 {
+            if (mask & 1 << bit) != 0 {
+                let value = D::store(depths.as_array_ref()[bit]);
+                let stored = &mut self.z_buffer[pixel_index + bit];
+
+                // Pixels can be drawn right at the far plane, or moved back to it
+                match (*stored == self.cleared, value == self.cleared) {
+                    (true, false) => self.uncovered -= 1,
+                    (false, true) => self.uncovered += 1,
+                    _ => (),
+                }
+                *stored = value;
+
+                let nearness = nearness.as_array_ref()[bit];
+                self.nearest = self.nearest.max(nearness);
+                // Only compares other than nearer can move a pixel farther away
+                self.farthest = self.farthest.min(nearness);
+            }
+        }
+
+        // Once every pixel has been written, the farthest depth can be found.
+        // Later writes keep it conservative, as above
+        if self.uncovered == 0 && self.farthest == NO_BOUND {
+            self.farthest = self
+                .z_buffer
+                .iter()
+                .fold(f32::INFINITY, |farthest, stored| {
+                    let nearness = self.depth_nearness(f32x4::splat(stored.load()));
+                    farthest.min(nearness.as_array_ref()[0])
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::U24;
+    use super::*;
+
+    #[test]
+    fn farther_writes_keep_the_bounds_conservative() {
+        let mut z_buffer = ZBuffer::<f32>::new(4, DepthConvention::Reversed { far: 100.0 });
+        z_buffer.write(0, f32x4::splat(1.0), 0b1111);
+        let farthest = z_buffer.farthest();
+
+        // Such as a draw with an Always or Farther compare
+        z_buffer.write(0, f32x4::splat(0.25), 0b0010);
+        assert!(z_buffer.farthest() < farthest);
+        assert_eq!(z_buffer.nearest(), farthest);
+    }
+
+    // Tests a fragment at 1/w against a stored 1/w, in every format and convention
+    fn nearer_passes<D: DepthFormat>(fragment: f32, stored: f32) -> [bool; 4] {
+        [
+            DepthConvention::Standard { far: 1000.0 },
+            DepthConvention::Reversed { far: 1000.0 },
+            DepthConvention::Standard { far: f32::INFINITY },
+            DepthConvention::Reversed { far: f32::INFINITY },
+        ]
+        .map(|convention| {
+            let mut z_buffer = ZBuffer::<D>::new(4, convention);
+            z_buffer.write(0, f32x4::splat(stored), 0b1111);
+
+            // The depth round trips back to 1/w
+            let error = (z_buffer.inverse_w(0) - stored).abs() / stored;
+            assert!(error < 0.01, "{convention:?}: {error}");
+
+            z_buffer.test(0, f32x4::splat(fragment), 0b1111, DepthCompare::Nearer) == 0b1111
+        })
+    }
+
+    #[test]
+    fn nearer_fragments_pass_in_every_format() {
+        // 1/w of a fragment 4 units away, and another 5 units away
+        let (near, far) = (1.0 / 4.0, 1.0 / 5.0);
+
+        assert_eq!(nearer_passes::<f32>(near, far), [true; 4]);
+        assert_eq!(nearer_passes::<u16>(near, far), [true; 4]);
+        assert_eq!(nearer_passes::<U24>(near, far), [true; 4]);
+
+        assert_eq!(nearer_passes::<f32>(far, near), [false; 4]);
+        assert_eq!(nearer_passes::<u16>(far, near), [false; 4]);
+        assert_eq!(nearer_passes::<U24>(far, near), [false; 4]);
+    }
+
+    #[test]
+    fn smaller_formats_lose_precision_far_away() {
+        // Two surfaces 100 and 100.5 units away, the standard convention
+        // packs far away depths together, more than 16 bits can separate
+        assert_eq!(separates::<u16>(), 0);
+        assert_eq!(separates::<U24>(), 0b1111);
+    }
+
+    fn separates<D: DepthFormat>() -> i32 {
+        let (near, far) = (1.0 / 100.0, 1.0 / 100.5);
+        let mut z_buffer = ZBuffer::<D>::new(4, DepthConvention::Standard { far: 1000.0 });
+
+        z_buffer.write(0, f32x4::splat(far), 0b1111);
+        z_buffer.test(0, f32x4::splat(near), 0b1111, DepthCompare::Nearer)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod animation;
+pub mod graphics;
+pub mod shaders;
@@ -3,13 +3,13 @@ use glam::Vec3A;
 use crate::graphics::Uniforms;
 use shared::types::Color;
 
-use super::PixelShader;
+use super::{diffuse_lighting, Derivatives, PixelShader};
 
 #[derive(Clone, Copy)]
 pub struct ColorBlend;
 
 impl PixelShader<3> for ColorBlend {
-    fn run(_: &Uniforms, parameters: [f32; 3]) -> Color {
+    fn run(_: &Uniforms, parameters: [f32; 3], _: &Derivatives<3>) -> Color {
         Color::from(parameters)
     }
 }
@@ -18,16 +18,19 @@ impl PixelShader<3> for ColorBlend {
 pub struct ColorBlendLit;
 
 impl PixelShader<9> for ColorBlendLit {
-    fn run(uniforms: &Uniforms, parameters: [f32; 9]) -> Color {
+    fn run(uniforms: &Uniforms, parameters: [f32; 9], _: &Derivatives<9>) -> Color {
         // Shader Setup
         let [r, g, b, norm_x, norm_y, norm_z, pixel_x, pixel_y, pixel_z] = parameters;
         let pixel_position = Vec3A::new(pixel_x, pixel_y, pixel_z);
         let normal = Vec3A::new(norm_x, norm_y, norm_z);
         let object_color = Vec3A::new(r, g, b);
 
-        let pixel_to_light = (uniforms.light_position - pixel_position).normalize();
-        let light_factor = f32::max(pixel_to_light.dot(normal) * uniforms.light_intensity, 0.0);
+        let lighting = diffuse_lighting(uniforms, pixel_position, normal);
 
-        Color::from(object_color * (light_factor + uniforms.ambient_light))
+        Color::from(object_color * (lighting + uniforms.ambient_light))
+    }
+
+    fn normal(parameters: &[f32; 9]) -> Option<Vec3A> {
+        Some(Vec3A::from_slice(&parameters[3..6]))
     }
 }
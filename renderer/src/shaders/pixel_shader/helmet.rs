@@ -0,0 +1,39 @@
+use glam::{Vec2, Vec3A};
+use shared::types::Color;
+
+use crate::graphics::Uniforms;
+
+use super::{diffuse_lighting, Derivatives, PixelShader};
+
+#[derive(Clone, Copy)]
+pub struct HelmetShader;
+
+impl PixelShader<8> for HelmetShader {
+    fn run(uniforms: &Uniforms, parameters: [f32; 8], derivatives: &Derivatives<8>) -> Color {
+        // Shader Setup
+        let [u, v, norm_x, norm_y, norm_z, pixel_x, pixel_y, pixel_z] = parameters;
+        let pixel_position = Vec3A::new(pixel_x, pixel_y, pixel_z);
+        let normal = Vec3A::new(norm_x, norm_y, norm_z);
+        let (uv, (ddx, ddy)) = (Vec2::new(u, v), derivatives.pair(0));
+        let diffuse = uniforms.diffuse.sample_grad(uv, ddx, ddy);
+        let object_color = Vec3A::from(diffuse);
+        let emissive = Vec3A::from(uniforms.emissive.sample_grad(uv, ddx, ddy));
+        let occlusion = Vec3A::from(uniforms.occlusion.sample_grad(uv, ddx, ddy));
+
+        // Lighting Calculations
+        // Meshes with tangents can use the normal mapped PbrShader instead
+
+        let lighting = diffuse_lighting(uniforms, pixel_position, normal);
+
+        let color = object_color * (lighting + (uniforms.ambient_light * occlusion)) + emissive;
+        Color::from(color.extend(diffuse.w))
+    }
+
+    fn normal(parameters: &[f32; 8]) -> Option<Vec3A> {
+        Some(Vec3A::from_slice(&parameters[2..5]))
+    }
+
+    fn tex_coords(parameters: &[f32; 8]) -> Option<Vec2> {
+        Some(Vec2::new(parameters[0], parameters[1]))
+    }
+}
@@ -0,0 +1,12 @@
+use glam::Vec3A;
+
+use crate::graphics::Uniforms;
+
+/// Lambertian diffuse lighting from every light of the draw, without the ambient light
+pub fn diffuse_lighting(uniforms: &Uniforms, position: Vec3A, normal: Vec3A) -> Vec3A {
+    uniforms
+        .incoming_lights(position)
+        .fold(Vec3A::ZERO, |total, incoming| {
+            total + incoming.radiance * incoming.to_light.dot(normal).max(0.0)
+        })
+}
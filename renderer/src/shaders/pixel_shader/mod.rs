@@ -0,0 +1,65 @@
+mod color_blend;
+pub use color_blend::*;
+
+mod textured;
+use shared::types::Color;
+pub use textured::*;
+
+mod helmet;
+pub use helmet::HelmetShader;
+
+mod pbr;
+pub use pbr::PbrShader;
+
+mod lighting;
+pub use lighting::diffuse_lighting;
+
+use glam::{Vec2, Vec3A};
+
+use crate::graphics::Uniforms;
+
+pub trait PixelShader<const PSIN: usize>: Copy + Send + Sync + 'static {
+    // Only the depth buffer is written, without running the shader
+    const DEPTH_ONLY: bool = false;
+
+    fn run(uniforms: &Uniforms, parameters: [f32; PSIN], derivatives: &Derivatives<PSIN>) -> Color;
+
+    // The interpolated surface normal and texture coordinates, shown by the debug views
+    // in place of the shaded color. Shaders without them are drawn black
+    fn normal(_parameters: &[f32; PSIN]) -> Option<Vec3A> {
+        None
+    }
+
+    fn tex_coords(_parameters: &[f32; PSIN]) -> Option<Vec2> {
+        None
+    }
+}
+
+/// Writes depth without shading anything, for rendering shadow maps
+#[derive(Clone, Copy)]
+pub struct DepthOnly;
+
+impl<const PSIN: usize> PixelShader<PSIN> for DepthOnly {
+    const DEPTH_ONLY: bool = true;
+
+    fn run(_: &Uniforms, _: [f32; PSIN], _: &Derivatives<PSIN>) -> Color {
+        unreachable!("Depth only draws never run their pixel shader")
+    }
+}
+
+// How much each parameter changes over one pixel in screen space,
+// shared by every pixel of a stamp. Used to pick texture mip levels
+pub struct Derivatives<const P: usize> {
+    pub dx: [f32; P],
+    pub dy: [f32; P],
+}
+
+impl<const P: usize> Derivatives<P> {
+    // The x and y derivatives of a pair of parameters, such as texture coordinates
+    pub fn pair(&self, index: usize) -> (Vec2, Vec2) {
+        (
+            Vec2::new(self.dx[index], self.dx[index + 1]),
+            Vec2::new(self.dy[index], self.dy[index + 1]),
+        )
+    }
+}
@@ -0,0 +1,109 @@
+use std::f32::consts::PI;
+
+use glam::{Mat3A, Vec2, Vec3A};
+use shared::types::Color;
+
+use crate::graphics::{IncomingLight, Uniforms};
+
+use super::{Derivatives, PixelShader};
+
+// Reflectance of dielectrics looking straight at the surface
+const DIELECTRIC_F0: f32 = 0.04;
+
+/// glTF metallic-roughness material, lit with a Cook-Torrance GGX specular.
+/// Expects the tangent basis from the PBR [`BaseVertexShader`](crate::shaders::BaseVertexShader)
+#[derive(Clone, Copy)]
+pub struct PbrShader;
+
+impl PixelShader<14> for PbrShader {
+    fn run(uniforms: &Uniforms, parameters: [f32; 14], derivatives: &Derivatives<14>) -> Color {
+        // Shader Setup
+        let [u, v, tx, ty, tz, bx, by, bz, nx, ny, nz, pixel_x, pixel_y, pixel_z] = parameters;
+        let pixel_position = Vec3A::new(pixel_x, pixel_y, pixel_z);
+        let tbn = Mat3A::from_cols(
+            Vec3A::new(tx, ty, tz),
+            Vec3A::new(bx, by, bz),
+            Vec3A::new(nx, ny, nz),
+        );
+
+        let (uv, (ddx, ddy)) = (Vec2::new(u, v), derivatives.pair(0));
+        let diffuse = uniforms.diffuse.sample_grad(uv, ddx, ddy);
+        let base_color = to_linear(Vec3A::from(diffuse));
+        let emissive = to_linear(Vec3A::from(uniforms.emissive.sample_grad(uv, ddx, ddy)));
+        let occlusion = uniforms.occlusion.sample_grad(uv, ddx, ddy).x;
+        let metallic_roughness = uniforms.metallic_roughness.sample_grad(uv, ddx, ddy);
+        let (roughness, metallic) = (metallic_roughness.y, metallic_roughness.z);
+
+        // Normal is in (0 -> 1) Ranges, and in tangent space
+        let normal = Vec3A::from(uniforms.normal.sample_grad(uv, ddx, ddy));
+        let normal = (tbn * ((normal * 2.0) - Vec3A::ONE)).normalize();
+
+        // Lighting Calculations
+        let to_camera = (uniforms.camera_position - pixel_position).normalize();
+        let n_dot_v = normal.dot(to_camera).max(1e-4);
+        let f0 = Vec3A::splat(DIELECTRIC_F0).lerp(base_color, metallic);
+
+        let direct =
+            uniforms
+                .incoming_lights(pixel_position)
+                .fold(Vec3A::ZERO, |total, incoming| {
+                    let IncomingLight { to_light, radiance } = incoming;
+                    let halfway = (to_light + to_camera).normalize();
+
+                    let n_dot_l = normal.dot(to_light).max(0.0);
+                    let n_dot_h = normal.dot(halfway).max(0.0);
+                    let v_dot_h = to_camera.dot(halfway).max(0.0);
+
+                    let fresnel = f0 + (Vec3A::ONE - f0) * (1.0 - v_dot_h).powi(5);
+
+                    let specular = fresnel
+                        * distribution_ggx(n_dot_h, roughness)
+                        * geometry_smith(n_dot_v, n_dot_l, roughness)
+                        / (4.0 * n_dot_v * n_dot_l).max(1e-4);
+                    let diffuse_weight = (Vec3A::ONE - fresnel) * (1.0 - metallic);
+
+                    // Scaled so a white diffuse surface facing the light matches the other lit shaders
+                    total + (diffuse_weight * base_color / PI + specular) * radiance * PI * n_dot_l
+                });
+
+        // Without an environment to reflect, metals reflect the ambient light in their own color
+        let ambient_reflectance = base_color * (1.0 - metallic) + f0;
+        let ambient = ambient_reflectance * uniforms.ambient_light * occlusion;
+
+        let color = to_gamma(direct + ambient + emissive);
+        Color::from(color.extend(diffuse.w))
+    }
+
+    fn normal(parameters: &[f32; 14]) -> Option<Vec3A> {
+        Some(Vec3A::from_slice(&parameters[8..11]))
+    }
+
+    fn tex_coords(parameters: &[f32; 14]) -> Option<Vec2> {
+        Some(Vec2::new(parameters[0], parameters[1]))
+    }
+}
+
+// Trowbridge-Reitz GGX normal distribution, with alpha as roughness squared
+fn distribution_ggx(n_dot_h: f32, roughness: f32) -> f32 {
+    let alpha_squared = roughness.powi(4);
+    let denominator = n_dot_h * n_dot_h * (alpha_squared - 1.0) + 1.0;
+    alpha_squared / (PI * denominator * denominator).max(1e-6)
+}
+
+// Schlick-GGX masking and shadowing, for both the light and view directions
+fn geometry_smith(n_dot_v: f32, n_dot_l: f32, roughness: f32) -> f32 {
+    let k = (roughness + 1.0).powi(2) / 8.0;
+    let schlick = |n_dot_x: f32| n_dot_x / (n_dot_x * (1.0 - k) + k);
+    schlick(n_dot_v) * schlick(n_dot_l)
+}
+
+// Colors are stored in sRGB, which is approximated with a gamma of 2
+// as pow is too slow to run for every pixel
+fn to_linear(color: Vec3A) -> Vec3A {
+    color * color
+}
+
+fn to_gamma(color: Vec3A) -> Vec3A {
+    let color = color.max(Vec3A::ZERO);
+    Vec3A::new(color.x.sqrt(), color.y.sqrt(), color.z.sqrt())
+}
@@ -0,0 +1,83 @@
+use glam::{Mat3A, Vec2, Vec3A};
+
+use crate::graphics::Uniforms;
+use shared::types::Color;
+
+use super::{diffuse_lighting, Derivatives, PixelShader};
+
+#[derive(Clone, Copy)]
+pub struct Textured;
+impl PixelShader<2> for Textured {
+    fn run(uniforms: &Uniforms, parameters: [f32; 2], derivatives: &Derivatives<2>) -> Color {
+        let [u, v] = parameters;
+        let (ddx, ddy) = derivatives.pair(0);
+        Color::from(uniforms.diffuse.sample_grad(Vec2::new(u, v), ddx, ddy))
+    }
+
+    fn tex_coords(parameters: &[f32; 2]) -> Option<Vec2> {
+        Some(Vec2::new(parameters[0], parameters[1]))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TexturedLit;
+
+impl PixelShader<8> for TexturedLit {
+    fn run(uniforms: &Uniforms, parameters: [f32; 8], derivatives: &Derivatives<8>) -> Color {
+        // Shader Setup
+        let [u, v, norm_x, norm_y, norm_z, pixel_x, pixel_y, pixel_z] = parameters;
+        let pixel_position = Vec3A::new(pixel_x, pixel_y, pixel_z);
+        let normal = Vec3A::new(norm_x, norm_y, norm_z);
+        let (uv, (ddx, ddy)) = (Vec2::new(u, v), derivatives.pair(0));
+        let diffuse = uniforms.diffuse.sample_grad(uv, ddx, ddy);
+        let object_color = Vec3A::from(diffuse);
+
+        let lighting = diffuse_lighting(uniforms, pixel_position, normal);
+
+        Color::from((object_color * (lighting + uniforms.ambient_light)).extend(diffuse.w))
+    }
+
+    fn normal(parameters: &[f32; 8]) -> Option<Vec3A> {
+        Some(Vec3A::from_slice(&parameters[2..5]))
+    }
+
+    fn tex_coords(parameters: &[f32; 8]) -> Option<Vec2> {
+        Some(Vec2::new(parameters[0], parameters[1]))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TexturedNormalMapLit;
+
+impl PixelShader<14> for TexturedNormalMapLit {
+    fn run(uniforms: &Uniforms, parameters: [f32; 14], derivatives: &Derivatives<14>) -> Color {
+        // Shader Setup
+        let [u, v, tx, ty, tz, bx, by, bz, nx, ny, nz, pixel_x, pixel_y, pixel_z] = parameters;
+        let pixel_position = Vec3A::new(pixel_x, pixel_y, pixel_z);
+        let tbn = Mat3A::from_cols(
+            Vec3A::new(tx, ty, tz),
+            Vec3A::new(bx, by, bz),
+            Vec3A::new(nx, ny, nz),
+        );
+        let (uv, (ddx, ddy)) = (Vec2::new(u, v), derivatives.pair(0));
+        let diffuse = uniforms.diffuse.sample_grad(uv, ddx, ddy);
+        let object_color = Vec3A::from(diffuse);
+
+        // Normal is in (0 -> 1) Ranges, and in tangent space
+        // So we need to put it in (-1 -> 1) Range
+        let normal = Vec3A::from(uniforms.normal.sample_grad(uv, ddx, ddy));
+        let normal = (tbn * ((normal * 2.0) - Vec3A::ONE)).normalize();
+
+        let lighting = diffuse_lighting(uniforms, pixel_position, normal);
+
+        Color::from((object_color * (lighting + uniforms.ambient_light)).extend(diffuse.w))
+    }
+
+    fn normal(parameters: &[f32; 14]) -> Option<Vec3A> {
+        Some(Vec3A::from_slice(&parameters[8..11]))
+    }
+
+    fn tex_coords(parameters: &[f32; 14]) -> Option<Vec2> {
+        Some(Vec2::new(parameters[0], parameters[1]))
+    }
+}
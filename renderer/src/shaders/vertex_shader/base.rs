@@ -1,4 +1,4 @@
-use glam::{Mat3, Vec3A, Vec4, Vec4Swizzles};
+use glam::{Vec3A, Vec4, Vec4Swizzles};
 use shared::vertex_parameters::VertexParameters;
 
 use crate::graphics::Uniforms;
@@ -112,15 +112,15 @@ impl VertexShader<5, 8> for BaseVertexShader {
     }
 }
 
-// Used for Textured & Lit via Normal Map
-impl VertexShader<8, 8> for BaseVertexShader {
+// Used for Textured & Lit via Normal Map, with a world space tangent basis
+impl VertexShader<8, 14> for BaseVertexShader {
     fn run(
         &self,
         _vertex_index: usize,
         uniforms: &Uniforms,
         position: Vec3A,
         input: [f32; 8],
-    ) -> VertexShaderOutput<8> {
+    ) -> VertexShaderOutput<14> {
         let [u, v, nx, ny, nz, tx, ty, tz] = input;
 
         let position = position.extend(1.0);
@@ -129,25 +129,31 @@ impl VertexShader<8, 8> for BaseVertexShader {
         let mvp = uniforms.projection * (uniforms.view * uniforms.model);
         let position = transform_point_to_clip_space(&position, &mvp);
 
-        let t = (uniforms.model * Vec4::new(tx, ty, tz, 0.0)).truncate();
-        let n = (uniforms.model * Vec4::new(nx, ny, nz, 0.0)).truncate();
+        let t = (uniforms.model * Vec4::new(tx, ty, tz, 0.0))
+            .xyz()
+            .normalize();
+        let n = (uniforms.model * Vec4::new(nx, ny, nz, 0.0))
+            .xyz()
+            .normalize();
         let b = n.cross(t);
-        let tbn = Mat3::from_cols(t, b, n).transpose();
-
-        let tan_light = tbn * uniforms.light_position;
-        let tan_pos = tbn * frag_position;
 
         VertexShaderOutput {
             position,
             parameters: VertexParameters([
                 u,
                 v,
-                tan_light.x,
-                tan_light.y,
-                tan_light.z,
-                tan_pos.x,
-                tan_pos.y,
-                tan_pos.z,
+                t.x,
+                t.y,
+                t.z,
+                b.x,
+                b.y,
+                b.z,
+                n.x,
+                n.y,
+                n.z,
+                frag_position.x,
+                frag_position.y,
+                frag_position.z,
             ]),
         }
     }
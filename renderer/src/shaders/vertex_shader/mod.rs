@@ -7,6 +7,9 @@ use shared::vertex_parameters::VertexParameters;
 mod animated;
 pub use animated::*;
 
+mod pbr;
+pub use pbr::*;
+
 use crate::graphics::Uniforms;
 
 pub struct VertexShaderOutput<const OUT: usize> {
@@ -0,0 +1,58 @@
+use glam::{Vec3A, Vec4, Vec4Swizzles};
+use shared::vertex_parameters::VertexParameters;
+
+use crate::graphics::Uniforms;
+
+use super::{transform_point_to_clip_space, VertexShader, VertexShaderOutput};
+
+/// Used for PBR, outputs a world space tangent basis for normal mapping
+#[derive(Default)]
+pub struct PbrVertexShader;
+
+// Takes the uvs, normal and tangent with its handedness,
+// outputs the uvs, tangent, bitangent, normal and world position
+impl VertexShader<9, 14> for PbrVertexShader {
+    fn run(
+        &self,
+        _vertex_index: usize,
+        uniforms: &Uniforms,
+        position: Vec3A,
+        input: [f32; 9],
+    ) -> VertexShaderOutput<14> {
+        let [u, v, nx, ny, nz, tx, ty, tz, handedness] = input;
+
+        let position = position.extend(1.0);
+        let frag_position = (uniforms.model * position).xyz();
+
+        let mvp = uniforms.projection * (uniforms.view * uniforms.model);
+        let position = transform_point_to_clip_space(&position, &mvp);
+
+        let n = (uniforms.model * Vec4::new(nx, ny, nz, 0.0))
+            .xyz()
+            .normalize();
+        let t = (uniforms.model * Vec4::new(tx, ty, tz, 0.0))
+            .xyz()
+            .normalize();
+        let b = n.cross(t) * handedness;
+
+        VertexShaderOutput {
+            position,
+            parameters: VertexParameters([
+                u,
+                v,
+                t.x,
+                t.y,
+                t.z,
+                b.x,
+                b.y,
+                b.z,
+                n.x,
+                n.y,
+                n.z,
+                frag_position.x,
+                frag_position.y,
+                frag_position.z,
+            ]),
+        }
+    }
+}
@@ -1,6 +1,5 @@
 use glam::Vec3A;
 use rkyv::{Archive, Deserialize, Serialize};
-use types::Color;
 
 pub mod animation;
 pub mod mesh;
@@ -24,7 +23,22 @@ pub struct IndexList(pub Box<[TriangleIndices]>);
 #[derive(Clone, Archive, Serialize, Deserialize)]
 pub struct VertexList(pub Box<[Vec3A]>);
 
+/// Includes an archive written by the exporter. rkyv requires the bytes
+/// to be aligned to access them in place, which `include_bytes!` doesn't guarantee.
+#[macro_export]
+macro_rules! include_archive {
+    ($path:literal) => {{
+        #[repr(C, align(16))]
+        struct Aligned<T: ?Sized>(T);
+
+        const ALIGNED: &Aligned<[u8]> = &Aligned(*include_bytes!($path));
+        &ALIGNED.0
+    }};
+}
+
 pub mod bytes {
+    pub use crate::include_archive;
+
     pub use super::animation::AnimationBytes;
     pub use super::mesh::MeshBytes;
     pub use super::skeleton::SkeletonBytes;
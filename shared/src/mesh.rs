@@ -2,11 +2,23 @@ use rkyv::{Archive, Deserialize, Serialize};
 
 use crate::{vertex_parameters::VertexParametersList, IndexList, VertexList};
 
+// How the mesh's material covers what's behind it, matching the glTF alpha modes
+#[derive(Serialize, Deserialize, Archive, Clone, Copy, Default, PartialEq, Debug)]
+#[archive_attr(derive(Clone, Copy, PartialEq, Debug))]
+pub enum AlphaMode {
+    #[default]
+    Opaque,
+    // Fragments with an alpha below the cutoff are discarded
+    Mask(f32),
+    Blend,
+}
+
 #[derive(Serialize, Deserialize, Archive)]
 pub struct Mesh<const PARAMETER_COUNT: usize> {
     pub vertices: VertexList,
     pub indices: IndexList,
     pub parameters: VertexParametersList<PARAMETER_COUNT>,
+    pub alpha_mode: AlphaMode,
 }
 
 pub struct MeshBytes<const PARAMETER_COUNT: usize>(pub &'static [u8]);
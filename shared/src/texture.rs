@@ -1,14 +1,32 @@
-use crate::Color;
-use glam::{UVec3, Vec3A};
+use glam::{Vec2, Vec4};
 use rkyv::{Archive, Deserialize, Serialize};
 
+// How texture coordinates outside of the 0 -> 1 range are handled,
+// matching the glTF sampler wrap modes
+#[derive(Serialize, Deserialize, Archive, Clone, Copy, Default, PartialEq, Debug)]
+#[archive_attr(derive(Clone, Copy))]
+pub enum WrapMode {
+    #[default]
+    Repeat,
+    ClampToEdge,
+    MirroredRepeat,
+}
+
 #[derive(Serialize, Deserialize, Archive)]
-pub struct Texture {
+pub struct MipLevel {
     pub width: u16,
     pub height: u16,
     pub data: Vec<u8>,
 }
 
+#[derive(Serialize, Deserialize, Archive)]
+pub struct Texture {
+    pub wrap_u: WrapMode,
+    pub wrap_v: WrapMode,
+    // The full size image first, each level after is half the size of the previous
+    pub mips: Vec<MipLevel>,
+}
+
 #[derive(Clone, Copy)]
 pub struct TextureBytes(pub &'static [u8]);
 
@@ -18,35 +36,86 @@ impl TextureBytes {
     }
 }
 
-impl ArchivedTexture {
-    const STRIDE: usize = 3;
+impl ArchivedWrapMode {
+    // Maps a texel coordinate into the 0 -> size range
+    fn wrap(self, texel: i32, size: i32) -> usize {
+        let wrapped = match self {
+            Self::Repeat => texel.rem_euclid(size),
+            Self::ClampToEdge => texel.clamp(0, size - 1),
+            Self::MirroredRepeat => {
+                let texel = texel.rem_euclid(size * 2);
+                if texel < size {
+                    texel
+                } else {
+                    (size * 2) - 1 - texel
+                }
+            }
+        };
+        wrapped as usize
+    }
+}
 
-    pub fn get_index(&self, u: f32, v: f32) -> usize {
-        let u = (u.abs().fract() * self.width as f32) as usize;
-        let v = (v.abs().fract() * self.height as f32) as usize;
+impl ArchivedMipLevel {
+    const STRIDE: usize = 4;
 
-        ((v * self.width as usize) + u) * Self::STRIDE
+    fn texel(&self, x: usize, y: usize) -> Vec4 {
+        let index = ((y * self.width as usize) + x) * Self::STRIDE;
+        let rgba = &self.data[index..index + Self::STRIDE];
+        Vec4::new(
+            rgba[0] as f32,
+            rgba[1] as f32,
+            rgba[2] as f32,
+            rgba[3] as f32,
+        )
     }
+}
 
-    pub fn index_veca(&self, index: usize) -> Vec3A {
-        let rgb = &self.data[index..index + Self::STRIDE];
-        UVec3::new(rgb[0] as u32, rgb[1] as u32, rgb[2] as u32).as_vec3a() / u8::MAX as f32
+impl ArchivedTexture {
+    pub fn width(&self) -> u16 {
+        self.mips[0].width
     }
 
-    pub fn index_color(&self, index: usize) -> Color {
-        let slice = &self.data[index..index + Self::STRIDE];
-        Color::new(slice[0], slice[1], slice[2])
+    pub fn height(&self) -> u16 {
+        self.mips[0].height
+    }
+
+    // Picks the mip level whose texels best match the size of a pixel,
+    // from the screen space derivatives of the texture coordinates
+    pub fn mip_level(&self, ddx: Vec2, ddy: Vec2) -> usize {
+        let size = Vec2::new(self.width() as f32, self.height() as f32);
+        let footprint = (ddx * size)
+            .length_squared()
+            .max((ddy * size).length_squared());
+
+        // Halved log2 of the squared length, rounded to the nearest level
+        let level = (footprint.log2() * 0.5).round().max(0.0) as usize;
+        level.min(self.mips.len() - 1)
     }
 
-    /// Simpler convenience functions, prefer get_index variants for performance
-    pub fn sample_vec(&self, u: f32, v: f32) -> Vec3A {
-        let index = self.get_index(u, v);
-        self.index_veca(index)
+    // Bilinearly filtered rgba in the 0 -> 1 range
+    pub fn sample(&self, uv: Vec2, level: usize) -> Vec4 {
+        let mip = &self.mips[level];
+        let (width, height) = (mip.width as i32, mip.height as i32);
+
+        // Texel centers are at half coordinates
+        let position = uv * Vec2::new(width as f32, height as f32) - 0.5;
+        let floor = position.floor();
+        let fraction = position - floor;
+        let (x, y) = (floor.x as i32, floor.y as i32);
+
+        let x0 = self.wrap_u.wrap(x, width);
+        let x1 = self.wrap_u.wrap(x + 1, width);
+        let y0 = self.wrap_v.wrap(y, height);
+        let y1 = self.wrap_v.wrap(y + 1, height);
+
+        let top = mip.texel(x0, y0).lerp(mip.texel(x1, y0), fraction.x);
+        let bottom = mip.texel(x0, y1).lerp(mip.texel(x1, y1), fraction.x);
+
+        top.lerp(bottom, fraction.y) / u8::MAX as f32
     }
 
-    /// Simpler convenience functions, prefer get_index variants for performance
-    pub fn sample_color(&self, u: f32, v: f32) -> Color {
-        let index = self.get_index(u, v);
-        self.index_color(index)
+    // Samples from the mip level selected by the texture coordinate derivatives
+    pub fn sample_grad(&self, uv: Vec2, ddx: Vec2, ddy: Vec2) -> Vec4 {
+        self.sample(uv, self.mip_level(ddx, ddy))
     }
 }
@@ -1,11 +1,11 @@
-use gamercade_rs::api::graphics_parameters::GraphicsParameters;
-use glam::Vec3A;
+use glam::{Vec3A, Vec4};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
     pub b: u8,
+    pub a: u8,
 }
 
 impl From<[f32; 3]> for Color {
@@ -14,6 +14,16 @@ impl From<[f32; 3]> for Color {
             r: (value[0].clamp(0.0, 1.0) * 255.0) as u8,
             g: (value[1].clamp(0.0, 1.0) * 255.0) as u8,
             b: (value[2].clamp(0.0, 1.0) * 255.0) as u8,
+            a: u8::MAX,
+        }
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from(value: [f32; 4]) -> Self {
+        Self {
+            a: (value[3].clamp(0.0, 1.0) * 255.0) as u8,
+            ..Self::from([value[0], value[1], value[2]])
         }
     }
 }
@@ -24,23 +34,19 @@ impl From<Vec3A> for Color {
     }
 }
 
+impl From<Vec4> for Color {
+    fn from(value: Vec4) -> Self {
+        Self::from(value.to_array())
+    }
+}
+
 impl Color {
+    // Fully opaque color
     pub const fn new(r: u8, g: u8, b: u8) -> Self {
-        Self { r, g, b }
+        Self::rgba(r, g, b, u8::MAX)
     }
 
-    pub fn to_graphics_params(self) -> GraphicsParameters {
-        let a_level = self.r / 8;
-        let g_level = self.g / 8;
-        let b_level = self.b / 16;
-
-        let g_palette = g_level / 4;
-        let g_color = (g_level % 4) * 16;
-
-        let r_palette = a_level * 8;
-
-        GraphicsParameters::default()
-            .palette_index(r_palette + g_palette)
-            .color_index(g_color + b_level)
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
     }
 }